@@ -0,0 +1,189 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct AnsiParser {
+    style: Style,
+}
+
+impl AnsiParser {
+    pub fn parse_line(&mut self, text: &str) -> Line<'static> {
+        let mut spans = Vec::new();
+        let mut run = String::new();
+        let mut column = 0;
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    if chars.peek() == Some(&'[') {
+                        chars.next();
+                        let mut params = String::new();
+                        let mut command = None;
+                        for c in chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&c) {
+                                command = Some(c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                        if command == Some('m') {
+                            if !run.is_empty() {
+                                spans.push(Span::styled(std::mem::take(&mut run), self.style));
+                            }
+                            self.apply_sgr(&params);
+                        }
+                    } else if chars.peek() == Some(&']') {
+                        while let Some(c) = chars.next() {
+                            if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                                break;
+                            }
+                        }
+                    } else {
+                        chars.next();
+                    }
+                }
+                '\t' => {
+                    let width = TAB_WIDTH - column % TAB_WIDTH;
+                    run.push_str(&" ".repeat(width));
+                    column += width;
+                }
+                '\r' => {}
+                c if c.is_control() => {}
+                c => {
+                    run.push(c);
+                    column += 1;
+                }
+            }
+        }
+
+        if !run.is_empty() {
+            spans.push(Span::styled(run, self.style));
+        }
+        Line::from(spans)
+    }
+
+    fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u16> = if params.is_empty() {
+            vec![0]
+        } else {
+            params.split([';', ':']).map(|code| code.parse().unwrap_or(0)).collect()
+        };
+
+        let mut codes = codes.into_iter();
+        while let Some(code) = codes.next() {
+            self.style = match code {
+                0 => Style::default(),
+                1 => self.style.add_modifier(Modifier::BOLD),
+                2 => self.style.add_modifier(Modifier::DIM),
+                3 => self.style.add_modifier(Modifier::ITALIC),
+                4 => self.style.add_modifier(Modifier::UNDERLINED),
+                5 => self.style.add_modifier(Modifier::SLOW_BLINK),
+                7 => self.style.add_modifier(Modifier::REVERSED),
+                8 => self.style.add_modifier(Modifier::HIDDEN),
+                9 => self.style.add_modifier(Modifier::CROSSED_OUT),
+                22 => self.style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+                23 => self.style.remove_modifier(Modifier::ITALIC),
+                24 => self.style.remove_modifier(Modifier::UNDERLINED),
+                25 => self.style.remove_modifier(Modifier::SLOW_BLINK),
+                27 => self.style.remove_modifier(Modifier::REVERSED),
+                28 => self.style.remove_modifier(Modifier::HIDDEN),
+                29 => self.style.remove_modifier(Modifier::CROSSED_OUT),
+                30..=37 => self.style.fg(Color::Indexed((code - 30) as u8)),
+                38 => match extended_color(&mut codes) {
+                    Some(color) => self.style.fg(color),
+                    None => self.style,
+                },
+                39 => self.style.fg(Color::Reset),
+                40..=47 => self.style.bg(Color::Indexed((code - 40) as u8)),
+                48 => match extended_color(&mut codes) {
+                    Some(color) => self.style.bg(color),
+                    None => self.style,
+                },
+                49 => self.style.bg(Color::Reset),
+                90..=97 => self.style.fg(Color::Indexed((code - 90 + 8) as u8)),
+                100..=107 => self.style.bg(Color::Indexed((code - 100 + 8) as u8)),
+                _ => self.style,
+            };
+        }
+    }
+}
+
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(codes.next()?.min(255) as u8)),
+        2 => {
+            let r = codes.next()?.min(255) as u8;
+            let g = codes.next()?.min(255) as u8;
+            let b = codes.next()?.min(255) as u8;
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(line: Line<'static>) -> Vec<(String, Style)> {
+        line.spans.into_iter().map(|span| (span.content.into_owned(), span.style)).collect()
+    }
+
+    #[test]
+    fn plain_text_is_one_span() {
+        let mut parser = AnsiParser::default();
+        assert_eq!(spans(parser.parse_line("hello\r")), [("hello".to_string(), Style::default())]);
+    }
+
+    #[test]
+    fn applies_colors_and_modifiers() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("a\x1b[1;31mb\x1b[22;94mc\x1b[0md");
+        assert_eq!(
+            spans(line),
+            [
+                ("a".to_string(), Style::default()),
+                ("b".to_string(), Style::default().fg(Color::Indexed(1)).add_modifier(Modifier::BOLD)),
+                ("c".to_string(), Style::default().fg(Color::Indexed(12)).remove_modifier(Modifier::BOLD | Modifier::DIM)),
+                ("d".to_string(), Style::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_extended_colors() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("\x1b[38;5;208mx\x1b[48;2;1;2;3my\x1b[38:2:300:0:0mz");
+        let styles: Vec<Style> = spans(line).into_iter().map(|(_, style)| style).collect();
+        assert_eq!(styles[0].fg, Some(Color::Indexed(208)));
+        assert_eq!(styles[1].bg, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(styles[2].fg, Some(Color::Rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn style_carries_over_to_the_next_line() {
+        let mut parser = AnsiParser::default();
+        parser.parse_line("\x1b[32mgreen");
+        let line = parser.parse_line("still");
+        assert_eq!(spans(line), [("still".to_string(), Style::default().fg(Color::Indexed(2)))]);
+    }
+
+    #[test]
+    fn strips_osc_and_other_sequences() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("\x1b]0;title\x07a\x1b]8;;https://example.com\x1b\\b\x1b[2Kc");
+        assert_eq!(spans(line), [("abc".to_string(), Style::default())]);
+    }
+
+    #[test]
+    fn expands_tabs_to_the_next_stop() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("ab\tc\t\x1b[1md");
+        assert_eq!(spans(line)[0].0, "ab  c   ");
+    }
+}
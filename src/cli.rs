@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: dott-tui [OPTIONS] [COMMAND]
+
+Commands:
+  init [--force]      Write a commented config template to the config path
+  check [PATH]        Validate a config file and report problems (alias: validate)
+
+Options:
+  -c, --config PATH   Use this config file instead of ~/.config/dott/config.toml
+  -p, --profile NAME  Use ~/.config/dott/profiles/NAME.toml
+  --list-themes       List bundled and user themes
+  -h, --help          Show this help
+  -V, --version       Show the version
+
+The DOTT_CONFIG environment variable sets the config file when no option is given.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run,
+    Init { force: bool },
+    Check,
+    ListThemes,
+    Help,
+    Version,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub config: Option<PathBuf>,
+    pub profile: Option<String>,
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        command: Command::Run,
+        config: None,
+        profile: None,
+    };
+    let mut check_path = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+
+        match flag.as_str() {
+            "-c" | "--config" => parsed.config = Some(PathBuf::from(value("--config")?)),
+            "-p" | "--profile" => {
+                let name = value("--profile")?;
+                if name.contains(['/', '\\']) {
+                    return Err(format!("invalid profile name \"{}\"", name));
+                }
+                parsed.profile = Some(name);
+            }
+            "--list-themes" => set_command(&mut parsed, Command::ListThemes)?,
+            "-h" | "--help" | "help" => set_command(&mut parsed, Command::Help)?,
+            "-V" | "--version" => set_command(&mut parsed, Command::Version)?,
+            "init" if parsed.command == Command::Run => parsed.command = Command::Init { force: false },
+            "-f" | "--force" if parsed.command == (Command::Init { force: false }) => {
+                parsed.command = Command::Init { force: true };
+            }
+            "check" | "validate" if parsed.command == Command::Run => parsed.command = Command::Check,
+            path if !path.starts_with('-') && parsed.command == Command::Check && check_path.is_none() => {
+                check_path = Some(PathBuf::from(path));
+            }
+            other if other.starts_with('-') => return Err(format!("unknown option \"{}\"", other)),
+            other => return Err(format!("unexpected argument \"{}\"", other)),
+        }
+    }
+
+    if parsed.config.is_some() && parsed.profile.is_some() {
+        return Err("--config and --profile cannot be used together".to_string());
+    }
+    if let Some(path) = check_path {
+        if parsed.config.is_some() || parsed.profile.is_some() {
+            return Err("give the file to check either as PATH or with --config/--profile".to_string());
+        }
+        parsed.config = Some(path);
+    }
+    Ok(parsed)
+}
+
+fn set_command(parsed: &mut Args, command: Command) -> Result<(), String> {
+    if parsed.command != Command::Run {
+        return Err("only one command can be given".to_string());
+    }
+    parsed.command = command;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Args, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn command(args: &[&str]) -> Command {
+        parse_args(args).unwrap().command
+    }
+
+    #[test]
+    fn runs_by_default() {
+        assert_eq!(
+            parse_args(&[]),
+            Ok(Args {
+                command: Command::Run,
+                config: None,
+                profile: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_config_and_profile() {
+        assert_eq!(parse_args(&["-c", "a.toml"]).unwrap().config, Some(PathBuf::from("a.toml")));
+        assert_eq!(parse_args(&["--config=b.toml"]).unwrap().config, Some(PathBuf::from("b.toml")));
+        assert_eq!(parse_args(&["-p", "work"]).unwrap().profile.as_deref(), Some("work"));
+        assert_eq!(parse_args(&["--profile=home"]).unwrap().profile.as_deref(), Some("home"));
+    }
+
+    #[test]
+    fn rejects_bad_config_and_profile() {
+        assert_eq!(parse_args(&["--config"]), Err("--config needs a value".to_string()));
+        assert_eq!(parse_args(&["--config="]), Err("--config needs a value".to_string()));
+        assert_eq!(parse_args(&["-p", "../x"]), Err("invalid profile name \"../x\"".to_string()));
+        assert_eq!(
+            parse_args(&["-c", "a.toml", "-p", "work"]),
+            Err("--config and --profile cannot be used together".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(command(&["init"]), Command::Init { force: false });
+        assert_eq!(command(&["init", "--force"]), Command::Init { force: true });
+        assert_eq!(command(&["check"]), Command::Check);
+        assert_eq!(command(&["validate"]), Command::Check);
+        assert_eq!(command(&["--list-themes"]), Command::ListThemes);
+        assert_eq!(command(&["help"]), Command::Help);
+        assert_eq!(command(&["-V"]), Command::Version);
+    }
+
+    #[test]
+    fn check_takes_an_optional_path() {
+        let args = parse_args(&["check", "x.toml"]).unwrap();
+        assert_eq!(args.command, Command::Check);
+        assert_eq!(args.config, Some(PathBuf::from("x.toml")));
+
+        let args = parse_args(&["-p", "work", "check"]).unwrap();
+        assert_eq!(args.profile.as_deref(), Some("work"));
+        assert_eq!(args.config, None);
+
+        assert_eq!(
+            parse_args(&["--config", "a.toml", "check", "b.toml"]),
+            Err("give the file to check either as PATH or with --config/--profile".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_extra_arguments() {
+        assert_eq!(parse_args(&["--bogus"]), Err("unknown option \"--bogus\"".to_string()));
+        assert_eq!(parse_args(&["--force"]), Err("unknown option \"--force\"".to_string()));
+        assert_eq!(parse_args(&["check", "a", "b"]), Err("unexpected argument \"b\"".to_string()));
+        assert_eq!(parse_args(&["init", "check"]), Err("unexpected argument \"check\"".to_string()));
+        assert_eq!(parse_args(&["init", "--help"]), Err("only one command can be given".to_string()));
+    }
+}
@@ -1,8 +1,16 @@
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+pub const TEMPLATE: &str = include_str!("template.toml");
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     #[serde(default = "default_structure")]
     pub structure: Structure,
@@ -15,6 +23,12 @@ pub struct Config {
 
     #[serde(default = "default_image_path")]
     pub image_logo_path: Option<String>,
+
+    #[serde(default)]
+    pub editor: Option<String>,
+
+    #[serde(default = "default_auto_reload")]
+    pub auto_reload: bool,
     
     #[serde(default = "default_entries")]
     pub entries: Vec<MenuItem>,
@@ -31,16 +45,63 @@ pub struct Config {
     #[serde(default)]
     pub entries5: Vec<MenuItem>,
 
+    #[serde(default)]
+    pub groups: BTreeMap<String, EntryGroup>,
+
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    #[serde(default)]
+    pub hotkeys: HotkeysConfig,
+
+    #[serde(default)]
+    pub mouse: MouseConfig,
+
+    #[serde(default)]
+    pub launch: LaunchConfig,
+
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyBinding>,
+
+    #[serde(default, deserialize_with = "deserialize_theme")]
+    pub theme: ThemeConfig,
+
     #[serde(default)]
     pub custom: Option<CustomModules>,
+
+    #[serde(skip)]
+    pub unknown_keys: Vec<Vec<KeySegment>>,
 }
 
+pub const MODULE_NAMES: &[&str] = &[
+    "logo",
+    "logo:default",
+    "logo:custom",
+    "logo:image",
+    "entries",
+    "entries2",
+    "entries3",
+    "entries4",
+    "entries5",
+    "colors",
+    "clock",
+    "help",
+    "break",
+    "selected",
+    "quit",
+    "system_info",
+    "quote",
+    "uptime",
+    "disk_usage",
+    "memory",
+];
+
 #[derive(Debug, Clone)]
 pub struct OrderedModule {
     pub module_type: ModuleType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ModuleType {
     Logo(LogoType),
     Entries(String),
@@ -57,24 +118,92 @@ pub enum ModuleType {
     Memory,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Structure {
     #[serde(default = "default_position")]
     pub position: Position,
+
+    #[serde(default = "default_vertical_position")]
+    pub vertical: VerticalPosition,
+
+    #[serde(default)]
+    pub margin: u16,
+
+    #[serde(default)]
+    pub padding: u16,
+
+    #[serde(default)]
+    pub max_width: Option<u16>,
     
     #[serde(default = "default_build")]
     pub build: Vec<StructureBuildItem>,
     
+    // Accepted so existing configs keep loading, but not used for drawing.
+    #[allow(dead_code)]
     #[serde(default)]
     pub font: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct StructureBuildItem {
-    pub module: String,
+    #[serde(default)]
+    pub module: Option<String>,
+
+    #[serde(default)]
+    pub row: Vec<StructureBuildItem>,
+
+    #[serde(default)]
+    pub column: Vec<StructureBuildItem>,
+
+    #[serde(default)]
+    pub grid: Vec<StructureBuildItem>,
+
+    #[serde(default)]
+    pub columns: Option<usize>,
+
+    #[serde(default)]
+    pub size: Option<Size>,
+
+    #[serde(default)]
+    pub align: Option<Position>,
+}
+
+impl StructureBuildItem {
+    fn module(name: &str) -> Self {
+        StructureBuildItem {
+            module: Some(name.to_string()),
+            row: Vec::new(),
+            column: Vec::new(),
+            grid: Vec::new(),
+            columns: None,
+            size: None,
+            align: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Size {
+    Cells(u16),
+    Spec(String),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Clone)]
+pub enum LayoutNode {
+    Module(OrderedModule),
+    Row(Vec<LayoutChild>),
+    Column(Vec<LayoutChild>),
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutChild {
+    pub size: Option<Size>,
+    pub align: Option<Position>,
+    pub node: LayoutNode,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum Position {
     Center,
@@ -82,52 +211,75 @@ pub enum Position {
     Right,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum VerticalPosition {
+    Top,
+    Middle,
+    Bottom,
+}
+
 fn default_position() -> Position {
     Position::Center
 }
 
+fn default_vertical_position() -> VerticalPosition {
+    VerticalPosition::Top
+}
+
 fn default_build() -> Vec<StructureBuildItem> {
     vec![
-        StructureBuildItem { module: "logo".to_string() },
-        StructureBuildItem { module: "entries".to_string() },
-        StructureBuildItem { module: "help".to_string() },
+        StructureBuildItem::module("logo"),
+        StructureBuildItem::module("entries"),
+        StructureBuildItem::module("help"),
     ]
 }
 
 fn default_structure() -> Structure {
     Structure {
         position: default_position(),
+        vertical: default_vertical_position(),
+        margin: 0,
+        padding: 0,
+        max_width: None,
         build: default_build(),
         font: None,
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct CustomModules {
     #[serde(default = "default_terminal_colors")]
     pub terminal_colors: TerminalColorsConfig,
 
+    // The modules below have no settings yet; the fields only make their
+    // tables valid keys.
+    #[allow(dead_code)]
     #[serde(default)]
     pub clock: ClockConfig,
 
-    #[serde(default = "default_break_config")]
+    #[serde(default = "default_break_config", rename = "break", alias = "break_")]
     pub break_: BreakConfig,
     
+    #[allow(dead_code)]
     #[serde(default)]
     pub selected: SelectedConfig,
     
+    #[allow(dead_code)]
     #[serde(default)]
     pub system_info: SystemInfoConfig,
     
     #[serde(default)]
     pub quote: QuoteConfig,
     
+    #[allow(dead_code)]
     #[serde(default)]
     pub uptime: UptimeConfig,
     
     #[serde(default)]
     pub disk_usage: DiskUsageConfig,
     
+    #[allow(dead_code)]
     #[serde(default)]
     pub memory: MemoryConfig,
 }
@@ -144,7 +296,7 @@ fn default_break_config() -> BreakConfig {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LogoType {
     Default,
@@ -164,46 +316,347 @@ fn default_image_path() -> Option<String> {
     None
 }
 
+fn default_auto_reload() -> bool {
+    true
+}
+
 fn default_entries() -> Vec<MenuItem> {
     vec![
         MenuItem {
             name: "View Dotfiles".to_string(),
             command: "yazi".to_string(),
             args: vec!["~/.config".to_string()],
+            ..Default::default()
         },
         MenuItem {
             name: "Edit Dott Config".to_string(),
-            command: "".to_string(),
-            args: vec![],
+            action: Some(EntryAction::EditConfig),
+            ..Default::default()
         },
         MenuItem {
             name: "View Shell".to_string(),
-            command: "".to_string(),
-            args: vec![],
+            action: Some(EntryAction::EditShellConfig),
+            ..Default::default()
         },
         MenuItem {
             name: "Quit".to_string(),
-            command: "".to_string(),
-            args: vec![],
+            action: Some(EntryAction::Quit),
+            ..Default::default()
         },
     ]
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EntryGroup {
+    #[serde(default)]
+    pub title: Option<String>,
+
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    #[serde(default)]
+    pub border: GroupBorder,
+
+    #[serde(default)]
+    pub collapsed: bool,
+
+    #[serde(default)]
+    pub entries: Vec<MenuItem>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBorder {
+    #[default]
+    None,
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct MenuItem {
     pub name: String,
+    #[serde(default)]
     pub command: String,
     #[serde(default)]
+    pub action: Option<EntryAction>,
+    #[serde(default)]
     pub args: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub shell: bool,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub clear_env: bool,
+    #[serde(default = "default_expand")]
+    pub expand: bool,
+    #[serde(default)]
+    pub pause: Option<PauseMode>,
+    #[serde(default)]
+    pub mode: LaunchMode,
+    #[serde(default)]
+    pub confirm: Option<Confirm>,
+}
+
+impl MenuItem {
+    pub fn builtin(&self) -> Option<EntryAction> {
+        if self.action.is_some() {
+            return self.action;
+        }
+        match self.name.as_str() {
+            "Quit" => Some(EntryAction::Quit),
+            "Edit Dott Config" => Some(EntryAction::EditConfig),
+            "View Shell" => Some(EntryAction::EditShellConfig),
+            _ => None,
+        }
+    }
+
+    pub fn confirm_message(&self) -> Option<String> {
+        match self.confirm {
+            Some(Confirm::Flag(true)) => Some(format!("Run \"{}\"?", self.name)),
+            Some(Confirm::Message(ref message)) => Some(message.clone()),
+            Some(Confirm::Flag(false)) | None => None,
+        }
+    }
+}
+
+fn default_expand() -> bool {
+    true
+}
+
+impl Default for MenuItem {
+    fn default() -> Self {
+        MenuItem {
+            name: String::new(),
+            command: String::new(),
+            action: None,
+            args: Vec::new(),
+            tags: Vec::new(),
+            key: None,
+            shell: false,
+            cwd: None,
+            env: BTreeMap::new(),
+            clear_env: false,
+            expand: default_expand(),
+            pause: None,
+            mode: LaunchMode::default(),
+            confirm: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SearchConfig {
+    #[serde(default)]
+    pub commands: bool,
+
+    #[serde(default = "default_search_tags")]
+    pub tags: bool,
+}
+
+fn default_search_tags() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HotkeysConfig {
+    #[serde(default = "default_hotkeys_enabled")]
+    pub numbers: bool,
+
+    #[serde(default = "default_hotkeys_enabled")]
+    pub show: bool,
+}
+
+fn default_hotkeys_enabled() -> bool {
+    true
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        HotkeysConfig {
+            numbers: default_hotkeys_enabled(),
+            show: default_hotkeys_enabled(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(flatten)]
+    pub styles: BTreeMap<String, StyleSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeSetting {
+    Name(String),
+    Inline(ThemeConfig),
+}
+
+fn deserialize_theme<'de, D>(deserializer: D) -> Result<ThemeConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match ThemeSetting::deserialize(deserializer)? {
+        ThemeSetting::Name(name) => ThemeConfig {
+            name: Some(name),
+            styles: BTreeMap::new(),
+        },
+        ThemeSetting::Inline(config) => config,
+    })
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum StyleSpec {
+    Color(ColorSpec),
+    Style(StyleTable),
+    // Anything else becomes a theme warning instead of failing the whole config.
+    Invalid(toml::Value),
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StyleTable {
+    #[serde(default)]
+    pub fg: Option<ColorSpec>,
+    #[serde(default)]
+    pub bg: Option<ColorSpec>,
+    #[serde(default)]
+    pub modifiers: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ColorSpec {
+    Index(i64),
+    Name(String),
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryAction {
+    Quit,
+    EditConfig,
+    EditShellConfig,
+    Reload,
+    Theme,
+    Search,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Confirm {
+    Flag(bool),
+    Message(String),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    pub fn keys(&self) -> Vec<String> {
+        match self {
+            KeyBinding::One(key) => vec![key.clone()],
+            KeyBinding::Many(keys) => keys.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MouseConfig {
+    #[serde(default = "default_mouse_enabled")]
+    pub enabled: bool,
+
+    #[serde(default = "default_mouse_enabled")]
+    pub hover: bool,
+}
+
+fn default_mouse_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LaunchConfig {
+    #[serde(default)]
+    pub pause: PauseMode,
+
+    #[serde(default = "default_show_success")]
+    pub show_success: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+fn default_show_success() -> bool {
+    true
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        LaunchConfig {
+            pause: PauseMode::default(),
+            show_success: default_show_success(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMode {
+    #[default]
+    Foreground,
+    Detach,
+    Background,
+    Capture,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PauseMode {
+    #[default]
+    Never,
+    Error,
+    Always,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        MouseConfig {
+            enabled: default_mouse_enabled(),
+            hover: default_mouse_enabled(),
+        }
+    }
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            commands: false,
+            tags: default_search_tags(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct TerminalColorsConfig {
     #[serde(default = "default_color_shape")]
     pub shape: ColorShape,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum ColorShape {
     Circles,
@@ -214,10 +667,10 @@ fn default_color_shape() -> ColorShape {
     ColorShape::Circles
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct ClockConfig {}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct BreakConfig {
     #[serde(default = "default_break_lines")]
     pub lines: usize,
@@ -227,13 +680,13 @@ fn default_break_lines() -> usize {
     2
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct SelectedConfig {}
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct SystemInfoConfig {}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct QuoteConfig {
     #[serde(default)]
     pub quotes: Vec<String>,
@@ -253,10 +706,10 @@ impl Default for QuoteConfig {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct UptimeConfig {}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct DiskUsageConfig {
     #[serde(default)]
     pub path: String,
@@ -270,7 +723,7 @@ impl Default for DiskUsageConfig {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct MemoryConfig {}
 
 impl Default for Config {
@@ -280,18 +733,33 @@ impl Default for Config {
             logo_type: default_logo_type(),
             custom_logo_path: default_logo_path(),
             image_logo_path: default_image_path(),
+            editor: None,
+            auto_reload: default_auto_reload(),
             entries: default_entries(),
             entries2: Vec::new(),
             entries3: Vec::new(),
             entries4: Vec::new(),
             entries5: Vec::new(),
+            groups: BTreeMap::new(),
+            search: SearchConfig::default(),
+            hotkeys: HotkeysConfig::default(),
+            mouse: MouseConfig::default(),
+            launch: LaunchConfig::default(),
+            keys: BTreeMap::new(),
+            theme: ThemeConfig::default(),
             custom: None,
+            unknown_keys: Vec::new(),
         }
     }
 }
 
 impl Config {
     pub fn get_entries(&self, name: &str) -> &[MenuItem] {
+        if let Some(group) = self.groups.get(name) {
+            if !group.entries.is_empty() {
+                return &group.entries;
+            }
+        }
         match name {
             "entries" => &self.entries,
             "entries2" => &self.entries2,
@@ -302,58 +770,122 @@ impl Config {
         }
     }
 
+    pub fn get_placed_entries(&self) -> Vec<(String, MenuItem)> {
+        let mut placed = Vec::new();
+        for module in self.get_ordered_modules() {
+            if let ModuleType::Entries(group) = module.module_type {
+                for entry in self.get_entries(&group) {
+                    placed.push((group.clone(), entry.clone()));
+                }
+            }
+        }
+        placed
+    }
+
+    pub fn get_group(&self, name: &str) -> Option<&EntryGroup> {
+        self.groups.get(name)
+    }
+
     pub fn get_ordered_modules(&self) -> Vec<OrderedModule> {
         let mut modules = Vec::new();
-        
-        for build_item in self.structure.build.iter() {
-            let module_name = &build_item.module;
-            let module_type = if module_name.starts_with("logo") {
-                let logo_type = if module_name == "logo" {
-                    self.logo_type.clone()
-                } else {
-                    let parts: Vec<&str> = module_name.split(':').collect();
-                    if parts.len() == 2 {
-                        match parts[1] {
-                            "default" => LogoType::Default,
-                            "custom" => LogoType::Custom,
-                            "image" => LogoType::Image,
-                            _ => self.logo_type.clone(),
-                        }
-                    } else {
-                        self.logo_type.clone()
+        for child in self.get_layout() {
+            collect_modules(&child.node, &mut modules);
+        }
+        modules
+    }
+
+    pub fn get_layout(&self) -> Vec<LayoutChild> {
+        self.structure
+            .build
+            .iter()
+            .filter_map(|item| self.layout_child(item))
+            .collect()
+    }
+
+    fn layout_child(&self, item: &StructureBuildItem) -> Option<LayoutChild> {
+        let node = if let Some(ref module_name) = item.module {
+            LayoutNode::Module(OrderedModule {
+                module_type: self.parse_module(module_name)?,
+            })
+        } else if !item.row.is_empty() {
+            LayoutNode::Row(item.row.iter().filter_map(|i| self.layout_child(i)).collect())
+        } else if !item.column.is_empty() {
+            LayoutNode::Column(item.column.iter().filter_map(|i| self.layout_child(i)).collect())
+        } else if !item.grid.is_empty() {
+            let cells: Vec<LayoutChild> = item.grid.iter().filter_map(|i| self.layout_child(i)).collect();
+            let columns = item.columns.unwrap_or(2).max(1);
+            let rows = cells
+                .chunks(columns)
+                .map(|chunk| {
+                    let mut row = chunk.to_vec();
+                    while row.len() < columns {
+                        row.push(LayoutChild {
+                            size: None,
+                            align: None,
+                            node: LayoutNode::Column(Vec::new()),
+                        });
                     }
-                };
-                Some(ModuleType::Logo(logo_type))
+                    LayoutChild {
+                        size: None,
+                        align: None,
+                        node: LayoutNode::Row(row),
+                    }
+                })
+                .collect();
+            LayoutNode::Column(rows)
+        } else {
+            return None;
+        };
+
+        Some(LayoutChild {
+            size: item.size.clone(),
+            align: item.align.clone(),
+            node,
+        })
+    }
+
+    pub fn parse_module(&self, module_name: &str) -> Option<ModuleType> {
+        if module_name.starts_with("logo") {
+            let logo_type = if module_name == "logo" {
+                self.logo_type.clone()
             } else {
-                match module_name.as_str() {
-                    "entries" => Some(ModuleType::Entries("entries".to_string())),
-                    "entries2" => Some(ModuleType::Entries("entries2".to_string())),
-                    "entries3" => Some(ModuleType::Entries("entries3".to_string())),
-                    "entries4" => Some(ModuleType::Entries("entries4".to_string())),
-                    "entries5" => Some(ModuleType::Entries("entries5".to_string())),
-                    "colors" => Some(ModuleType::Colors),
-                    "clock" => Some(ModuleType::Clock),
-                    "help" => Some(ModuleType::Help),
-                    "break" => Some(ModuleType::Break),
-                    "selected" => Some(ModuleType::Selected),
-                    "quit" => Some(ModuleType::Quit),
-                    "system_info" | "systeminfo" => Some(ModuleType::SystemInfo),
-                    "quote" => Some(ModuleType::Quote),
-                    "uptime" => Some(ModuleType::Uptime),
-                    "disk_usage" | "diskusage" | "disk" => Some(ModuleType::DiskUsage),
-                    "memory" | "mem" => Some(ModuleType::Memory),
-                    _ => None,
+                let parts: Vec<&str> = module_name.split(':').collect();
+                if parts.len() == 2 {
+                    match parts[1] {
+                        "default" => LogoType::Default,
+                        "custom" => LogoType::Custom,
+                        "image" => LogoType::Image,
+                        _ => self.logo_type.clone(),
+                    }
+                } else {
+                    self.logo_type.clone()
                 }
             };
-            
-            if let Some(mt) = module_type {
-                modules.push(OrderedModule {
-                    module_type: mt,
-                });
+            Some(ModuleType::Logo(logo_type))
+        } else {
+            match module_name {
+                "entries" => Some(ModuleType::Entries("entries".to_string())),
+                "entries2" => Some(ModuleType::Entries("entries2".to_string())),
+                "entries3" => Some(ModuleType::Entries("entries3".to_string())),
+                "entries4" => Some(ModuleType::Entries("entries4".to_string())),
+                "entries5" => Some(ModuleType::Entries("entries5".to_string())),
+                name if name.starts_with("entries:") => {
+                    Some(ModuleType::Entries(name["entries:".len()..].to_string()))
+                }
+                "colors" => Some(ModuleType::Colors),
+                "clock" => Some(ModuleType::Clock),
+                "help" => Some(ModuleType::Help),
+                "break" => Some(ModuleType::Break),
+                "selected" => Some(ModuleType::Selected),
+                "quit" => Some(ModuleType::Quit),
+                "system_info" | "systeminfo" => Some(ModuleType::SystemInfo),
+                "quote" => Some(ModuleType::Quote),
+                "uptime" => Some(ModuleType::Uptime),
+                "disk_usage" | "diskusage" | "disk" => Some(ModuleType::DiskUsage),
+                "memory" | "mem" => Some(ModuleType::Memory),
+                _ => None,
             }
         }
-        
-        modules
     }
 
     pub fn get_break_lines(&self) -> usize {
@@ -364,50 +896,303 @@ impl Config {
     }
 }
 
+pub fn available_profiles() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(Config::profiles_dir()) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+fn collect_modules(node: &LayoutNode, modules: &mut Vec<OrderedModule>) {
+    match node {
+        LayoutNode::Module(module) => modules.push(module.clone()),
+        LayoutNode::Row(children) | LayoutNode::Column(children) => {
+            for child in children {
+                collect_modules(&child.node, modules);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub location: Option<(usize, usize)>,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some((line, column)) = self.location {
+            write!(f, ":{}:{}", line, column)?;
+        }
+        if let Some(ref key) = self.key {
+            write!(f, ": {}", key)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySegment {
+    Key(String),
+    Index(usize),
+}
+
+fn key_segments(path: &serde_ignored::Path, segments: &mut Vec<KeySegment>) {
+    match path {
+        serde_ignored::Path::Root => {}
+        serde_ignored::Path::Seq { parent, index } => {
+            key_segments(parent, segments);
+            segments.push(KeySegment::Index(*index));
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            key_segments(parent, segments);
+            segments.push(KeySegment::Key(key.clone()));
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => key_segments(parent, segments),
+    }
+}
+
+pub fn readable_message(message: &str) -> String {
+    let expected = match message.trim().strip_prefix("data did not match any variant of untagged enum ") {
+        Some("ThemeSetting") => Some("expected a theme name or a table of theme roles"),
+        Some("ColorSpec") => Some("expected a color name, a \"#rrggbb\" value or a number from 0 to 255"),
+        Some("Confirm") => Some("expected true, false or a question to ask"),
+        Some("KeyBinding") => Some("expected a key or a list of keys"),
+        Some("Size") => Some("expected a number of cells or a size like \"30%\", \"2fr\" or \"1/3\""),
+        Some(_) => Some("unexpected value"),
+        None => None,
+    };
+    match expected {
+        Some(expected) => expected.to_string(),
+        None => message.trim().lines().collect::<Vec<_>>().join(", "),
+    }
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
 impl Config {
-    pub fn load() -> Self {
+    pub fn load() -> Result<Self, ConfigError> {
         let config_path = Self::config_path();
         
-        if config_path.exists() {
-            match fs::read_to_string(&config_path) {
-                Ok(content) => match toml::from_str(&content) {
-                    Ok(config) => config,
-                    Err(e) => {
-                        eprintln!("Error parsing config: {}. Using defaults.", e);
-                        Config::default()
-                    }
-                },
-                Err(e) => {
-                    eprintln!("Error reading config: {}. Using defaults.", e);
-                    Config::default()
-                }
-            }
+        if config_path.exists() || !Self::is_default_path() {
+            Self::load_from(&config_path)
         } else {
-            let config = Config::default();
-            let _ = config.save();
-            config
+            Ok(Config::default())
         }
     }
 
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_path = Self::config_path();
-        
-        if let Some(parent) = config_path.parent() {
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|e| ConfigError {
+            path: path.to_path_buf(),
+            location: None,
+            key: None,
+            message: e.to_string(),
+        })?;
+        Self::parse(&content, path)
+    }
+
+    pub fn parse(content: &str, path: &Path) -> Result<Self, ConfigError> {
+        let mut unknown_keys = Vec::new();
+        let mut record = |ignored: serde_ignored::Path| {
+            let mut segments = Vec::new();
+            key_segments(&ignored, &mut segments);
+            unknown_keys.push(segments);
+        };
+        let deserializer = serde_ignored::Deserializer::new(toml::Deserializer::new(content), &mut record);
+        let result: Result<Config, _> = serde_path_to_error::deserialize(deserializer);
+        let mut config = result.map_err(|e| {
+            let key = e.path().to_string();
+            let location = e.inner().span().map(|span| line_column(content, span.start));
+            ConfigError {
+                path: path.to_path_buf(),
+                location,
+                key: if key == "." { None } else { Some(key) },
+                message: readable_message(e.inner().message()),
+            }
+        })?;
+        config.unknown_keys = unknown_keys;
+        Ok(config)
+    }
+
+    pub fn write_template(path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        
-        let content = toml::to_string_pretty(self)?;
-        fs::write(&config_path, content)?;
-        Ok(())
+        fs::write(path, TEMPLATE)
+    }
+
+    pub fn themes_dir() -> PathBuf {
+        if PROFILE.get().is_some() && CONFIG_PATH.get().is_none() {
+            return Self::dott_dir().join("themes");
+        }
+        let config_path = Self::config_path();
+        config_path
+            .parent()
+            .map(|dir| dir.join("themes"))
+            .unwrap_or_else(|| PathBuf::from("themes"))
     }
 
-    fn config_path() -> PathBuf {
+    pub fn config_path() -> PathBuf {
+        if let Some(path) = CONFIG_PATH.get() {
+            return path.clone();
+        }
+        if let Some(profile) = PROFILE.get() {
+            return Self::profiles_dir().join(format!("{}.toml", profile));
+        }
+        match std::env::var("DOTT_CONFIG") {
+            Ok(path) if !path.is_empty() => PathBuf::from(crate::expand::expand(&path)),
+            _ => Self::dott_dir().join("config.toml"),
+        }
+    }
+
+    pub fn profiles_dir() -> PathBuf {
+        Self::dott_dir().join("profiles")
+    }
+
+    pub fn set_config_path(path: PathBuf) {
+        CONFIG_PATH.set(path).expect("config path is set once at startup");
+    }
+
+    pub fn set_profile(name: &str) {
+        PROFILE.set(name.to_string()).expect("profile is set once at startup");
+    }
+
+    pub fn is_default_path() -> bool {
+        CONFIG_PATH.get().is_none()
+            && PROFILE.get().is_none()
+            && std::env::var("DOTT_CONFIG").map_or(true, |path| path.is_empty())
+    }
+
+    fn dott_dir() -> PathBuf {
         let config_dir = std::env::var("XDG_CONFIG_HOME")
             .map(PathBuf::from)
             .unwrap_or_else(|_| {
                 let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
                 PathBuf::from(home).join(".config")
             });
-        config_dir.join("dott").join("config.toml")
+        config_dir.join("dott")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(content: &str) -> ConfigError {
+        Config::parse(content, Path::new("config.toml")).expect_err("config should not parse")
+    }
+
+    #[test]
+    fn syntax_error_has_line_and_column() {
+        let error = parse_error("[[entries]]\nname = \"Broken\"\ncommand = \"ls\n");
+        assert_eq!(error.location, Some((3, 14)));
+        assert_eq!(error.key, None);
+    }
+
+    #[test]
+    fn type_error_names_the_key() {
+        let error = parse_error("[[entries]]\nname = \"Htop\"\nmode = \"sideways\"\n");
+        assert_eq!(error.location, Some((3, 8)));
+        assert_eq!(error.key.as_deref(), Some("entries[0].mode"));
+        assert!(error.to_string().starts_with("config.toml:3:8: entries[0].mode: unknown variant `sideways`"));
+    }
+
+    #[test]
+    fn untagged_errors_do_not_name_internal_types() {
+        let error = parse_error("theme = 5\n");
+        assert_eq!(error.key.as_deref(), Some("theme"));
+        assert_eq!(error.message, "expected a theme name or a table of theme roles");
+
+        let error = parse_error("[[entries]]\nname = \"Reboot\"\nconfirm = 3\n");
+        assert_eq!(error.message, "expected true, false or a question to ask");
+    }
+
+    fn parse(content: &str) -> Config {
+        Config::parse(content, Path::new("config.toml")).expect("config should parse")
+    }
+
+    fn names(entries: &[MenuItem]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_module_names() {
+        let config = parse("logo_type = \"custom\"\n");
+        assert_eq!(config.parse_module("entries:git"), Some(ModuleType::Entries("git".to_string())));
+        assert_eq!(config.parse_module("entries2"), Some(ModuleType::Entries("entries2".to_string())));
+        assert_eq!(config.parse_module("logo"), Some(ModuleType::Logo(LogoType::Custom)));
+        assert_eq!(config.parse_module("logo:image"), Some(ModuleType::Logo(LogoType::Image)));
+        assert_eq!(config.parse_module("disk"), Some(ModuleType::DiskUsage));
+        assert_eq!(config.parse_module("entries6"), None);
+    }
+
+    #[test]
+    fn group_entries_win_over_legacy_entries() {
+        let config = parse(
+            "[[groups.entries2.entries]]\nname = \"Grouped\"\ncommand = \"a\"\n\n[[entries2]]\nname = \"Legacy\"\ncommand = \"b\"\n\n[[groups.git.entries]]\nname = \"Status\"\ncommand = \"git\"\n",
+        );
+        assert_eq!(names(config.get_entries("entries2")), ["Grouped"]);
+        assert_eq!(names(config.get_entries("git")), ["Status"]);
+        assert!(config.get_entries("servers").is_empty());
+    }
+
+    #[test]
+    fn group_without_entries_keeps_legacy_entries() {
+        let config = parse(
+            "[groups.entries2]\ntitle = \"Tools\"\ncollapsed = true\n\n[[entries2]]\nname = \"Legacy\"\ncommand = \"b\"\n",
+        );
+        assert_eq!(names(config.get_entries("entries2")), ["Legacy"]);
+        let group = config.get_group("entries2").expect("group metadata is kept");
+        assert_eq!(group.title.as_deref(), Some("Tools"));
+        assert!(group.collapsed);
+    }
+
+    #[test]
+    fn builtin_action_prefers_the_action_key() {
+        let entry = |name: &str, action: Option<EntryAction>| MenuItem {
+            name: name.to_string(),
+            action,
+            ..Default::default()
+        };
+        assert_eq!(entry("Beenden", Some(EntryAction::Quit)).builtin(), Some(EntryAction::Quit));
+        assert_eq!(entry("Quit", Some(EntryAction::Reload)).builtin(), Some(EntryAction::Reload));
+        assert_eq!(entry("Quit", None).builtin(), Some(EntryAction::Quit));
+        assert_eq!(entry("Edit Dott Config", None).builtin(), Some(EntryAction::EditConfig));
+        assert_eq!(entry("View Shell", None).builtin(), Some(EntryAction::EditShellConfig));
+        assert_eq!(entry("quit", None).builtin(), None);
+        assert_eq!(entry("Htop", None).builtin(), None);
+    }
+
+    #[test]
+    fn parses_builtin_actions() {
+        let config = parse("[[entries]]\nname = \"Settings\"\naction = \"edit_config\"\n\n[[entries]]\nname = \"Filter\"\naction = \"search\"\n");
+        let actions: Vec<_> = config.entries.iter().map(MenuItem::builtin).collect();
+        assert_eq!(actions, [Some(EntryAction::EditConfig), Some(EntryAction::Search)]);
+    }
+
+    #[test]
+    fn line_column_counts_characters() {
+        assert_eq!(line_column("a = 1\nb = \"é\" x", 14), (2, 8));
+        assert_eq!(line_column("abc", 0), (1, 1));
     }
 }
@@ -0,0 +1,219 @@
+use crate::config::MenuItem;
+use crate::exec;
+use crate::prompt::{self, Kind, Placeholder};
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::HashMap;
+
+const MAX_WIDTH: u16 = 60;
+const MAX_CHOICES: usize = 10;
+
+pub struct Confirm {
+    pub entry: MenuItem,
+    pub args: Vec<String>,
+    pub message: String,
+    pub yes: bool,
+}
+
+impl Confirm {
+    pub fn new(entry: &MenuItem, args: Vec<String>, message: String) -> Confirm {
+        Confirm {
+            entry: entry.clone(),
+            args,
+            message,
+            yes: false,
+        }
+    }
+}
+
+pub struct Prompt {
+    pub entry: MenuItem,
+    placeholders: Vec<Placeholder>,
+    answers: HashMap<String, String>,
+    step: usize,
+    input: String,
+    choice: usize,
+}
+
+impl Prompt {
+    pub fn new(entry: &MenuItem, placeholders: Vec<Placeholder>) -> Prompt {
+        Prompt {
+            entry: entry.clone(),
+            placeholders,
+            answers: HashMap::new(),
+            step: 0,
+            input: String::new(),
+            choice: 0,
+        }
+    }
+
+    pub fn is_choice(&self) -> bool {
+        matches!(self.current().kind, Kind::Choice(_))
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+
+    pub fn move_choice(&mut self, forward: bool) {
+        if let Kind::Choice(ref options) = self.current().kind {
+            let len = options.len();
+            self.choice = if forward { (self.choice + 1) % len } else { (self.choice + len - 1) % len };
+        }
+    }
+
+    pub fn submit(&mut self) -> Option<Vec<String>> {
+        let placeholder = self.current().clone();
+        let answer = match placeholder.kind {
+            Kind::Prompt(_) => std::mem::take(&mut self.input),
+            Kind::Choice(ref options) => options[self.choice].clone(),
+        };
+        self.answers.insert(placeholder.token, answer);
+        self.choice = 0;
+        self.step += 1;
+
+        if self.step < self.placeholders.len() {
+            return None;
+        }
+        Some(prompt::substitute(&exec::expand_args(&self.entry), &self.answers))
+    }
+
+    fn current(&self) -> &Placeholder {
+        &self.placeholders[self.step]
+    }
+}
+
+pub fn draw_confirm(f: &mut Frame, area: Rect, confirm: &Confirm, theme: &Theme) {
+    let button = |label: &str, selected: bool| {
+        let style = if selected { theme.entry_selected } else { theme.entry };
+        Span::styled(format!("[ {} ]", label), style)
+    };
+
+    let mut lines: Vec<Line> = confirm
+        .message
+        .lines()
+        .map(|line| Line::from(Span::styled(line.to_string(), theme.entry)))
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        button("Yes", confirm.yes),
+        Span::raw("   "),
+        button("No", !confirm.yes),
+    ]));
+
+    let text_width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16;
+    let width = text_width.saturating_add(6).min(MAX_WIDTH).min(area.width);
+    let text_rows = confirm
+        .message
+        .lines()
+        .map(|line| (line.chars().count() as u16).div_ceil(width.saturating_sub(4).max(1)).max(1))
+        .sum::<u16>();
+    let dialog = centered(area, width, text_rows.saturating_add(4));
+
+    f.render_widget(Clear, dialog);
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(dialog_block(&confirm.entry.name, theme)),
+        dialog,
+    );
+}
+
+pub fn draw_prompt(f: &mut Frame, area: Rect, prompt: &Prompt, theme: &Theme) {
+    let width = MAX_WIDTH.min(area.width);
+    let inner_width = width.saturating_sub(4) as usize;
+
+    let mut lines = Vec::new();
+    match prompt.current().kind {
+        Kind::Prompt(ref label) => {
+            let input: Vec<char> = prompt.input.chars().collect();
+            let visible: String = input[input.len().saturating_sub(inner_width.saturating_sub(1))..]
+                .iter()
+                .collect();
+            lines.push(Line::from(Span::styled(format!("{}:", label), theme.group_title)));
+            lines.push(Line::from(vec![
+                Span::styled(visible, theme.entry),
+                Span::styled("▏", theme.accent),
+            ]));
+        }
+        Kind::Choice(ref options) => {
+            let first = prompt.choice.saturating_sub(MAX_CHOICES - 1);
+            for (index, option) in options.iter().enumerate().skip(first).take(MAX_CHOICES) {
+                let line = if index == prompt.choice {
+                    Span::styled(format!("> {}", option), theme.entry_selected)
+                } else {
+                    Span::styled(format!("  {}", option), theme.entry)
+                };
+                lines.push(Line::from(line));
+            }
+        }
+    }
+    if prompt.placeholders.len() > 1 {
+        lines.push(Line::from(Span::styled(
+            format!("{}/{}", prompt.step + 1, prompt.placeholders.len()),
+            theme.muted,
+        )));
+    }
+
+    let dialog = centered(area, width, lines.len() as u16 + 2);
+
+    f.render_widget(Clear, dialog);
+    f.render_widget(Paragraph::new(lines).block(dialog_block(&prompt.entry.name, theme)), dialog);
+}
+
+fn dialog_block<'a>(title: &str, theme: &Theme) -> Block<'a> {
+    Block::bordered()
+        .border_set(border::ROUNDED)
+        .border_style(theme.accent)
+        .padding(Padding::horizontal(1))
+        .title(Line::from(Span::styled(format!(" {} ", title), theme.group_title)))
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expand::expand;
+
+    #[test]
+    fn answers_are_not_expanded() {
+        let entry = MenuItem {
+            name: "Echo".to_string(),
+            command: "echo".to_string(),
+            args: vec!["~/notes".to_string(), "{prompt:Text}".to_string()],
+            ..Default::default()
+        };
+        let mut prompt = Prompt::new(&entry, prompt::placeholders(&entry.args));
+        for c in "$HOME ~".chars() {
+            prompt.push_char(c);
+        }
+
+        let args = prompt.submit().expect("single placeholder finishes the prompt");
+        assert_eq!(args, [expand("~/notes"), "$HOME ~".to_string()]);
+
+        let command = exec::build_command(&entry, &args);
+        let built: Vec<_> = command.get_args().collect();
+        assert_eq!(built, [expand("~/notes").as_str(), "$HOME ~"]);
+    }
+}
@@ -0,0 +1,400 @@
+use crate::config::{LaunchMode, MenuItem, PauseMode};
+use crate::expand::expand;
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub message: String,
+    pub success: bool,
+    pub finished: Instant,
+}
+
+#[derive(Debug)]
+pub struct Job {
+    pub group: String,
+    pub name: String,
+    child: Child,
+    shell: bool,
+    started: Instant,
+}
+
+impl Job {
+    pub fn poll(&mut self) -> Option<Report> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(exit_report(&self.name, status, self.started.elapsed(), self.shell)),
+            Ok(None) => None,
+            Err(e) => Some(report(format!("{}: {}", self.name, e), false)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Capture {
+    name: String,
+    child: Child,
+    receiver: Receiver<String>,
+    shell: bool,
+    started: Instant,
+}
+
+impl Capture {
+    pub fn poll(&mut self, lines: &mut Vec<String>) -> Option<Report> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(line) => lines.push(line),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(exit_report(&self.name, status, self.started.elapsed(), self.shell)),
+            Ok(None) => None,
+            Err(e) => Some(report(format!("{}: {}", self.name, e), false)),
+        }
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+pub fn expand_args(entry: &MenuItem) -> Vec<String> {
+    if entry.expand {
+        entry.args.iter().map(|arg| expand(arg)).collect()
+    } else {
+        entry.args.clone()
+    }
+}
+
+pub fn build_command(entry: &MenuItem, args: &[String]) -> Command {
+    let expand_arg = |arg: &String| if entry.expand { expand(arg) } else { arg.clone() };
+
+    let mut command = if entry.shell {
+        let mut script = entry.command.clone();
+        for arg in args {
+            script.push(' ');
+            script.push_str(&shell_quote(arg));
+        }
+        let mut command = Command::new(user_shell());
+        command.arg("-c").arg(script);
+        command
+    } else {
+        let mut command = Command::new(expand_arg(&entry.command));
+        command.args(args);
+        command
+    };
+
+    if entry.clear_env {
+        command.env_clear();
+    }
+    command.envs(&entry.env);
+
+    if let Some(ref cwd) = entry.cwd {
+        command.current_dir(expand_arg(cwd));
+    }
+
+    command
+}
+
+pub fn run_entry(entry: &MenuItem, args: &[String]) -> Report {
+    let mut command = build_command(entry, args);
+    if let Some(report) = check_cwd(entry, &command) {
+        return report;
+    }
+    run(&mut command, &entry.name, entry.shell)
+}
+
+pub fn run(command: &mut Command, label: &str, shell: bool) -> Report {
+    let started = Instant::now();
+
+    match command.status() {
+        Ok(status) => exit_report(label, status, started.elapsed(), shell),
+        Err(e) => spawn_error(label, command, e),
+    }
+}
+
+pub fn spawn_entry(entry: &MenuItem, args: &[String], group: &str) -> Result<Option<Job>, Report> {
+    let mut command = build_command(entry, args);
+    if let Some(report) = check_cwd(entry, &command) {
+        return Err(report);
+    }
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn().map_err(|e| spawn_error(&entry.name, &command, e))?;
+    if entry.mode == LaunchMode::Background {
+        return Ok(Some(Job {
+            group: group.to_string(),
+            name: entry.name.clone(),
+            child,
+            shell: entry.shell,
+            started: Instant::now(),
+        }));
+    }
+
+    std::thread::spawn(move || child.wait());
+    Ok(None)
+}
+
+pub fn capture_entry(entry: &MenuItem, args: &[String]) -> Result<Capture, Report> {
+    let mut command = build_command(entry, args);
+    if let Some(report) = check_cwd(entry, &command) {
+        return Err(report);
+    }
+    command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = command.spawn().map_err(|e| spawn_error(&entry.name, &command, e))?;
+    let (sender, receiver) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, sender);
+    }
+
+    Ok(Capture {
+        name: entry.name.clone(),
+        child,
+        receiver,
+        shell: entry.shell,
+        started: Instant::now(),
+    })
+}
+
+fn forward_lines<R: Read + Send + 'static>(reader: R, sender: Sender<String>) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if buffer.last() == Some(&b'\n') {
+                        buffer.pop();
+                    }
+                    if sender.send(String::from_utf8_lossy(&buffer).into_owned()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+pub fn started(entry: &MenuItem) -> Report {
+    report(format!("{} started", entry.name), true)
+}
+
+fn check_cwd(entry: &MenuItem, command: &Command) -> Option<Report> {
+    let dir = command.get_current_dir()?;
+    if dir.is_dir() {
+        return None;
+    }
+    Some(report(
+        format!("{}: working directory {} not found", entry.name, dir.display()),
+        false,
+    ))
+}
+
+fn spawn_error(label: &str, command: &Command, e: io::Error) -> Report {
+    let program = command.get_program().to_string_lossy();
+    let message = match e.kind() {
+        ErrorKind::NotFound => format!("{}: command not found: {}", label, program),
+        ErrorKind::PermissionDenied => format!("{}: permission denied: {}", label, program),
+        _ => format!("{}: failed to run {}: {}", label, program, e),
+    };
+    report(message, false)
+}
+
+fn exit_report(label: &str, status: ExitStatus, elapsed: Duration, shell: bool) -> Report {
+    let elapsed = format_duration(elapsed);
+    match status.code() {
+        Some(0) => report(format!("{} finished in {}", label, elapsed), true),
+        Some(127) if shell => report(format!("{}: command not found", label), false),
+        Some(code) => report(
+            format!("{} exited with code {} after {}", label, code, elapsed),
+            false,
+        ),
+        None => report(format!("{} was terminated after {}", label, elapsed), false),
+    }
+}
+
+fn report(message: String, success: bool) -> Report {
+    Report {
+        message,
+        success,
+        finished: Instant::now(),
+    }
+}
+
+pub fn should_pause(mode: PauseMode, report: &Report) -> bool {
+    match mode {
+        PauseMode::Never => false,
+        PauseMode::Error => !report.success,
+        PauseMode::Always => true,
+    }
+}
+
+pub fn wait_for_key(report: &Report) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    writeln!(stdout)?;
+    writeln!(stdout, "[{}]", report.message)?;
+    write!(stdout, "Press any key to return to dott...")?;
+    stdout.flush()?;
+
+    enable_raw_mode()?;
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break Ok(()),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    result
+}
+
+pub fn editor_command(configured: Option<&str>, path: &Path) -> Command {
+    let editor = configured
+        .map(str::to_string)
+        .into_iter()
+        .chain(["VISUAL", "EDITOR"].iter().filter_map(|name| std::env::var(name).ok()))
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            let fallback = if find_in_path("nvim") { "nvim" } else { "vi" };
+            fallback.to_string()
+        });
+
+    let mut parts = editor.split_whitespace().map(expand);
+    let mut command = Command::new(parts.next().unwrap_or_default());
+    command.args(parts).arg(path);
+    command
+}
+
+pub fn find_in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+pub fn user_shell() -> String {
+    std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| "/bin/sh".to_string())
+}
+
+fn format_duration(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    if seconds >= 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_opt_out_keeps_args_literal() {
+        let mut entry = MenuItem {
+            name: "Show".to_string(),
+            command: "git".to_string(),
+            args: vec!["~/x".to_string(), "HEAD~1".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(expand_args(&entry), [expand("~/x"), "HEAD~1".to_string()]);
+
+        entry.expand = false;
+        assert_eq!(expand_args(&entry), ["~/x", "HEAD~1"]);
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(shell_quote("plain-arg_1.txt"), "plain-arg_1.txt");
+        assert_eq!(shell_quote("key=value"), "key=value");
+        assert_eq!(shell_quote("two words"), "'two words'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote("a;b"), "'a;b'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn shell_mode_runs_one_quoted_script() {
+        let entry = MenuItem {
+            name: "Log".to_string(),
+            command: "git log | head".to_string(),
+            shell: true,
+            ..Default::default()
+        };
+        let command = build_command(&entry, &args(&["--grep", "it's $x"]));
+        assert_eq!(command.get_program(), user_shell().as_str());
+        let built: Vec<_> = command.get_args().collect();
+        assert_eq!(built, ["-c", "git log | head --grep 'it'\\''s $x'"]);
+    }
+
+    #[test]
+    fn direct_mode_passes_arguments_unchanged() {
+        let entry = MenuItem {
+            name: "Echo".to_string(),
+            command: "echo".to_string(),
+            ..Default::default()
+        };
+        let command = build_command(&entry, &args(&["two words", "$HOME"]));
+        assert_eq!(command.get_program(), "echo");
+        let built: Vec<_> = command.get_args().collect();
+        assert_eq!(built, ["two words", "$HOME"]);
+    }
+
+    #[test]
+    fn applies_cwd_and_env() {
+        let mut entry = MenuItem {
+            name: "Build".to_string(),
+            command: "make".to_string(),
+            cwd: Some("~/src".to_string()),
+            env: [("MODE".to_string(), "release".to_string())].into_iter().collect(),
+            ..Default::default()
+        };
+        let command = build_command(&entry, &[]);
+        assert_eq!(command.get_current_dir(), Some(Path::new(&expand("~/src"))));
+        let envs: Vec<_> = command.get_envs().collect();
+        assert_eq!(envs, [("MODE".as_ref(), Some("release".as_ref()))]);
+
+        entry.expand = false;
+        let command = build_command(&entry, &[]);
+        assert_eq!(command.get_current_dir(), Some(Path::new("~/src")));
+    }
+}
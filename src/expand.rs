@@ -0,0 +1,129 @@
+use std::fs;
+
+pub fn expand(input: &str) -> String {
+    expand_vars(&expand_tilde(input), |name| std::env::var(name).ok())
+}
+
+fn expand_tilde(input: &str) -> String {
+    let Some(rest) = input.strip_prefix('~') else {
+        return input.to_string();
+    };
+    let (user, tail) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+
+    let home = if user.is_empty() {
+        std::env::var("HOME").ok()
+    } else {
+        fs::read_to_string("/etc/passwd").ok().and_then(|passwd| user_home(user, &passwd))
+    };
+
+    match home {
+        Some(home) => format!("{}{}", home, tail),
+        None => input.to_string(),
+    }
+}
+
+fn user_home(user: &str, passwd: &str) -> Option<String> {
+    passwd.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields.as_slice() {
+            [name, _, _, _, _, home, ..] if *name == user => Some(home.to_string()),
+            _ => None,
+        }
+    })
+}
+
+fn expand_vars(input: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(index) = rest.find('$') {
+        output.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            output.push('$');
+            rest = after;
+            continue;
+        }
+
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+
+        match lookup(name) {
+            Some(value) if !name.is_empty() => output.push_str(&value),
+            _ => output.push_str(&rest[index..index + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn home() -> String {
+        std::env::var("HOME").expect("HOME is set")
+    }
+
+    #[test]
+    fn expands_leading_tilde_only() {
+        assert_eq!(expand("~"), home());
+        assert_eq!(expand("~/notes"), format!("{}/notes", home()));
+        assert_eq!(expand("HEAD~1"), "HEAD~1");
+        assert_eq!(expand("foo~bar"), "foo~bar");
+    }
+
+    #[test]
+    fn reads_home_from_passwd() {
+        let passwd = "root:x:0:0:root:/root:/bin/sh\n# comment\nalice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh\nbroken:x\n";
+        assert_eq!(user_home("alice", passwd).as_deref(), Some("/home/alice"));
+        assert_eq!(user_home("root", passwd).as_deref(), Some("/root"));
+        assert_eq!(user_home("ali", passwd), None);
+        assert_eq!(user_home("broken", passwd), None);
+    }
+
+    #[test]
+    fn unknown_user_is_kept() {
+        assert_eq!(expand("~dott-no-such-user/x"), "~dott-no-such-user/x");
+    }
+
+    fn vars(input: &str) -> String {
+        let values = HashMap::from([("NAME", "value"), ("EMPTY", "")]);
+        expand_vars(input, |name| values.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn expands_variables() {
+        assert_eq!(vars("$NAME/x"), "value/x");
+        assert_eq!(vars("${NAME}suffix"), "valuesuffix");
+        assert_eq!(vars("a-$NAME-b"), "a-value-b");
+        assert_eq!(vars("[$EMPTY]"), "[]");
+    }
+
+    #[test]
+    fn keeps_unset_escaped_and_unclosed_variables() {
+        assert_eq!(vars("$UNSET/x"), "$UNSET/x");
+        assert_eq!(vars("${UNSET}"), "${UNSET}");
+        assert_eq!(vars("$NAMEX"), "$NAMEX");
+        assert_eq!(vars("cost $$5"), "cost $5");
+        assert_eq!(vars("${NAME"), "${NAME");
+        assert_eq!(vars("trailing $"), "trailing $");
+        assert_eq!(vars("${}"), "${}");
+    }
+}
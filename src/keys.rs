@@ -0,0 +1,509 @@
+use crate::config::{HotkeysConfig, KeyBinding, MenuItem};
+use crate::validate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    First,
+    Last,
+    PageUp,
+    PageDown,
+    Launch,
+    Search,
+    ToggleGroup,
+    CycleTheme,
+    Reload,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = &[
+        Action::Up,
+        Action::Down,
+        Action::First,
+        Action::Last,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Launch,
+        Action::Search,
+        Action::ToggleGroup,
+        Action::CycleTheme,
+        Action::Reload,
+        Action::Quit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::First => "first",
+            Action::Last => "last",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Launch => "launch",
+            Action::Search => "search",
+            Action::ToggleGroup => "toggle_group",
+            Action::CycleTheme => "theme",
+            Action::Reload => "reload",
+            Action::Quit => "quit",
+        }
+    }
+
+    fn label(&self) -> Option<&'static str> {
+        match self {
+            Action::Up => Some("Up"),
+            Action::Down => Some("Down"),
+            Action::Launch => Some("Select"),
+            Action::Search => Some("Filter"),
+            Action::ToggleGroup => Some("Toggle Group"),
+            Action::CycleTheme => Some("Theme"),
+            Action::Reload => Some("Reload Config"),
+            Action::Quit => Some("Quit"),
+            Action::First | Action::Last | Action::PageUp | Action::PageDown => None,
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Up => &["up", "k"],
+            Action::Down => &["down", "j"],
+            Action::First => &["home"],
+            Action::Last => &["end"],
+            Action::PageUp => &["pageup", "ctrl+u"],
+            Action::PageDown => &["pagedown", "ctrl+d"],
+            Action::Launch => &["enter"],
+            Action::Search => &["/"],
+            Action::ToggleGroup => &["space"],
+            Action::CycleTheme => &["t"],
+            Action::Reload => &["u"],
+            Action::Quit => &["q", "esc"],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bindings {
+    bindings: Vec<(Action, Vec<KeyChord>)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::from_config(&BTreeMap::new()).0
+    }
+}
+
+impl Bindings {
+    pub fn from_config(keys: &BTreeMap<String, KeyBinding>) -> (Bindings, Vec<String>) {
+        let mut warnings = Vec::new();
+
+        for name in keys.keys() {
+            if !Action::ALL.iter().any(|action| action.name() == name) {
+                let names: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+                match validate::suggest(name, names.iter().copied()) {
+                    Some(suggestion) => warnings.push(format!(
+                        "Unknown key action \"{}\", did you mean \"{}\"?",
+                        name, suggestion
+                    )),
+                    None => warnings.push(format!(
+                        "Unknown key action \"{}\", available actions: {}",
+                        name,
+                        names.join(", ")
+                    )),
+                }
+            }
+        }
+
+        let mut bindings: Vec<(Action, Vec<KeyChord>)> = Vec::new();
+        for action in Action::ALL {
+            let specs: Vec<String> = match keys.get(action.name()) {
+                Some(binding) => binding.keys(),
+                None => action.default_keys().iter().map(|k| k.to_string()).collect(),
+            };
+
+            let mut chords = Vec::new();
+            for spec in specs {
+                match KeyChord::parse(&spec) {
+                    Ok(chord) => {
+                        let taken = bindings
+                            .iter()
+                            .find(|(_, existing)| existing.contains(&chord))
+                            .map(|(other, _)| *other);
+                        match taken {
+                            Some(other) => warnings.push(format!(
+                                "Key \"{}\" for \"{}\" is already bound to \"{}\"",
+                                chord,
+                                action.name(),
+                                other.name()
+                            )),
+                            None => chords.push(chord),
+                        }
+                    }
+                    Err(e) => warnings.push(format!("Key action \"{}\": {}", action.name(), e)),
+                }
+            }
+            bindings.push((*action, chords));
+        }
+
+        (Bindings { bindings }, warnings)
+    }
+
+    pub fn action_for(&self, event: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, chords)| chords.iter().any(|chord| chord.matches(event)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn chords(&self) -> Vec<KeyChord> {
+        self.bindings.iter().flat_map(|(_, chords)| chords.iter().copied()).collect()
+    }
+
+    pub fn help_text(&self) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, chords)| !chords.is_empty())
+            .filter_map(|(action, chords)| {
+                let keys: Vec<String> = chords.iter().map(|chord| chord.to_string()).collect();
+                action.label().map(|label| format!("{}: {}", keys.join("/"), label))
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+}
+
+impl KeyChord {
+    pub fn parse(spec: &str) -> Result<KeyChord, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err("empty key".to_string());
+        }
+
+        let parts: Vec<&str> = if spec == "+" {
+            vec!["+"]
+        } else {
+            spec.split('+').collect()
+        };
+        let (key, modifier_names) = parts.split_last().ok_or_else(|| format!("invalid key \"{}\"", spec))?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names {
+            modifiers |= match name.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier \"{}\" in \"{}\"", other, spec)),
+            };
+        }
+
+        let code = parse_code(key).ok_or_else(|| format!("unknown key \"{}\" in \"{}\"", key, spec))?;
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            other => other,
+        };
+
+        Ok(KeyChord { code, modifiers })
+    }
+
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        match (self.code, event.code) {
+            (KeyCode::Char(a), KeyCode::Char(b)) => {
+                a == b && self.modifiers == event.modifiers.difference(KeyModifiers::SHIFT)
+            }
+            (a, b) => a == b && self.modifiers == event.modifiers,
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "backtab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+fn parse_code(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let lower = key.trim().to_lowercase();
+    let code = match lower.as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[derive(Debug, Clone)]
+pub struct Hotkey {
+    pub chord: KeyChord,
+    pub index: usize,
+}
+
+pub fn assign_hotkeys(
+    entries: &[(String, MenuItem)],
+    config: &HotkeysConfig,
+    bindings: &Bindings,
+) -> (Vec<Hotkey>, Vec<String>) {
+    let builtins = bindings.chords();
+    let mut hotkeys: Vec<Hotkey> = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, (_, entry)) in entries.iter().enumerate() {
+        let Some(ref spec) = entry.key else {
+            continue;
+        };
+        let chord = match KeyChord::parse(spec) {
+            Ok(chord) => chord,
+            Err(e) => {
+                warnings.push(format!("Entry \"{}\": {}", entry.name, e));
+                continue;
+            }
+        };
+        if builtins.contains(&chord) {
+            warnings.push(format!(
+                "Entry \"{}\": key \"{}\" is already used by dott and was ignored",
+                entry.name, chord
+            ));
+        } else if let Some(existing) = hotkeys.iter().find(|h| h.chord == chord) {
+            warnings.push(format!(
+                "Entry \"{}\": key \"{}\" is already used by \"{}\"",
+                entry.name, chord, entries[existing.index].1.name
+            ));
+        } else {
+            hotkeys.push(Hotkey { chord, index });
+        }
+    }
+
+    if config.numbers {
+        for (index, digit) in (0..entries.len().min(9)).zip('1'..='9') {
+            let chord = KeyChord {
+                code: KeyCode::Char(digit),
+                modifiers: KeyModifiers::NONE,
+            };
+            if !hotkeys.iter().any(|h| h.chord == chord) && !builtins.contains(&chord) {
+                hotkeys.push(Hotkey { chord, index });
+            }
+        }
+    }
+
+    (hotkeys, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord { code, modifiers }
+    }
+
+    fn entry(name: &str, key: Option<&str>) -> (String, MenuItem) {
+        let item = MenuItem {
+            name: name.to_string(),
+            command: "true".to_string(),
+            key: key.map(str::to_string),
+            ..Default::default()
+        };
+        ("entries".to_string(), item)
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(KeyChord::parse("g"), Ok(chord(KeyCode::Char('g'), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("ctrl+g"), Ok(chord(KeyCode::Char('g'), KeyModifiers::CONTROL)));
+        assert_eq!(KeyChord::parse("Ctrl+Alt+x"), Ok(chord(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT)));
+        assert_eq!(KeyChord::parse("shift+g"), Ok(chord(KeyCode::Char('G'), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("shift+tab"), Ok(chord(KeyCode::Tab, KeyModifiers::SHIFT)));
+        assert_eq!(KeyChord::parse("f5"), Ok(chord(KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("F12"), Ok(chord(KeyCode::F(12), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("+"), Ok(chord(KeyCode::Char('+'), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("space"), Ok(chord(KeyCode::Char(' '), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn rejects_bad_chords() {
+        assert!(KeyChord::parse("").is_err());
+        assert!(KeyChord::parse("hyper+g").is_err());
+        assert!(KeyChord::parse("fx").is_err());
+        assert!(KeyChord::parse("banana").is_err());
+    }
+
+    #[test]
+    fn matches_key_events() {
+        let upper = KeyChord::parse("G").unwrap();
+        assert!(upper.matches(&KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+        assert!(!upper.matches(&KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE)));
+
+        let ctrl = KeyChord::parse("ctrl+g").unwrap();
+        assert!(ctrl.matches(&KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL)));
+        assert!(!ctrl.matches(&KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE)));
+
+        let f5 = KeyChord::parse("f5").unwrap();
+        assert!(f5.matches(&KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE)));
+        assert!(!f5.matches(&KeyEvent::new(KeyCode::F(5), KeyModifiers::SHIFT)));
+    }
+
+    #[test]
+    fn assigns_entry_keys_and_numbers() {
+        let entries = [entry("Git", Some("g")), entry("Htop", None)];
+        let (hotkeys, warnings) = assign_hotkeys(&entries, &HotkeysConfig::default(), &Bindings::default());
+        assert!(warnings.is_empty());
+
+        let find = |code| hotkeys.iter().find(|h| h.chord.code == code).map(|h| h.index);
+        assert_eq!(find(KeyCode::Char('g')), Some(0));
+        assert_eq!(find(KeyCode::Char('1')), Some(0));
+        assert_eq!(find(KeyCode::Char('2')), Some(1));
+        assert_eq!(find(KeyCode::Char('3')), None);
+    }
+
+    #[test]
+    fn reports_hotkey_conflicts() {
+        let entries = [
+            entry("Git", Some("g")),
+            entry("Grep", Some("g")),
+            entry("Quit Now", Some("q")),
+            entry("Broken", Some("hyper+x")),
+        ];
+        let config = HotkeysConfig {
+            numbers: false,
+            show: true,
+        };
+        let (hotkeys, warnings) = assign_hotkeys(&entries, &config, &Bindings::default());
+        assert_eq!(hotkeys.len(), 1);
+        assert_eq!(
+            warnings,
+            [
+                "Entry \"Grep\": key \"g\" is already used by \"Git\"",
+                "Entry \"Quit Now\": key \"q\" is already used by dott and was ignored",
+                "Entry \"Broken\": unknown modifier \"hyper\" in \"hyper+x\"",
+            ]
+        );
+    }
+
+    fn bindings(keys: &[(&str, &[&str])]) -> (Bindings, Vec<String>) {
+        let keys = keys
+            .iter()
+            .map(|(action, specs)| {
+                let specs = specs.iter().map(|spec| spec.to_string()).collect();
+                (action.to_string(), KeyBinding::Many(specs))
+            })
+            .collect();
+        Bindings::from_config(&keys)
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn default_bindings() {
+        let bindings = Bindings::default();
+        assert_eq!(bindings.action_for(&press(KeyCode::Char('j'))), Some(Action::Down));
+        assert_eq!(bindings.action_for(&press(KeyCode::Esc)), Some(Action::Quit));
+        assert_eq!(
+            bindings.action_for(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::PageDown)
+        );
+        assert_eq!(bindings.action_for(&press(KeyCode::Char('x'))), None);
+    }
+
+    #[test]
+    fn custom_bindings_replace_the_defaults() {
+        let (bindings, warnings) = bindings(&[("quit", &["ctrl+c"]), ("reload", &[])]);
+        assert!(warnings.is_empty());
+        assert_eq!(
+            bindings.action_for(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(bindings.action_for(&press(KeyCode::Char('q'))), None);
+        assert_eq!(bindings.action_for(&press(KeyCode::Char('u'))), None);
+    }
+
+    #[test]
+    fn conflicting_binding_keeps_the_first_action() {
+        let (bindings, warnings) = bindings(&[("up", &["j"])]);
+        assert_eq!(warnings, ["Key \"j\" for \"down\" is already bound to \"up\""]);
+        assert_eq!(bindings.action_for(&press(KeyCode::Char('j'))), Some(Action::Up));
+        assert_eq!(bindings.action_for(&press(KeyCode::Down)), Some(Action::Down));
+    }
+
+    #[test]
+    fn invalid_binding_is_reported() {
+        let (_, warnings) = bindings(&[("search", &["hyper+f"])]);
+        assert_eq!(warnings, ["Key action \"search\": unknown modifier \"hyper\" in \"hyper+f\""]);
+    }
+
+    #[test]
+    fn help_text_lists_labelled_actions() {
+        let (bindings, _) = bindings(&[("quit", &["q", "ctrl+c"]), ("theme", &[])]);
+        let help = bindings.help_text();
+        assert!(help.starts_with("↑/k: Up | ↓/j: Down | enter: Select"), "{}", help);
+        assert!(help.ends_with("q/ctrl+c: Quit"), "{}", help);
+        assert!(!help.contains("Theme"), "{}", help);
+        assert!(!help.contains("home"), "{}", help);
+    }
+
+    #[test]
+    fn unknown_action_suggests_a_close_name() {
+        let keys = BTreeMap::from([("qiut".to_string(), KeyBinding::One("x".to_string()))]);
+        let (_, warnings) = Bindings::from_config(&keys);
+        assert_eq!(warnings, ["Unknown key action \"qiut\", did you mean \"quit\"?"]);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::config::{LayoutChild, LayoutNode, ModuleType, Position, Size, Structure, VerticalPosition};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Margin, Rect},
+    text::Line,
+    widgets::Paragraph,
+    Frame,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Placement {
+    pub area: Rect,
+    pub alignment: Alignment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Entry(usize),
+    Group(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hit {
+    pub area: Rect,
+    pub target: Target,
+}
+
+#[derive(Default)]
+pub struct Content {
+    pub lines: Vec<Line<'static>>,
+    pub targets: Vec<(usize, Target)>,
+}
+
+pub enum Block {
+    Lines(Content),
+    Row(Vec<Cell>),
+    Column(Vec<Cell>),
+}
+
+pub struct Cell {
+    pub size: Option<Size>,
+    pub align: Option<Position>,
+    pub block: Block,
+}
+
+impl Block {
+    pub fn build<F>(children: &[LayoutChild], render: &mut F) -> Block
+    where
+        F: FnMut(&ModuleType) -> Content,
+    {
+        Block::Column(Self::build_cells(children, render))
+    }
+
+    fn build_cells<F>(children: &[LayoutChild], render: &mut F) -> Vec<Cell>
+    where
+        F: FnMut(&ModuleType) -> Content,
+    {
+        children
+            .iter()
+            .map(|child| Cell {
+                size: child.size.clone(),
+                align: child.align.clone(),
+                block: match &child.node {
+                    LayoutNode::Module(module) => Block::Lines(render(&module.module_type)),
+                    LayoutNode::Row(children) => Block::Row(Self::build_cells(children, render)),
+                    LayoutNode::Column(children) => Block::Column(Self::build_cells(children, render)),
+                },
+            })
+            .collect()
+    }
+
+    fn width(&self) -> u16 {
+        match self {
+            Block::Lines(content) => {
+                let width = content.lines.iter().map(|line| line.width()).max().unwrap_or(0);
+                width.min(u16::MAX as usize) as u16
+            }
+            Block::Row(_) => u16::MAX,
+            Block::Column(cells) => cells.iter().map(|cell| cell.block.width()).max().unwrap_or(0),
+        }
+    }
+
+    fn height(&self) -> u16 {
+        match self {
+            Block::Lines(content) => content.lines.len().min(u16::MAX as usize) as u16,
+            Block::Row(cells) => cells.iter().map(|cell| cell.block.height()).max().unwrap_or(0),
+            Block::Column(cells) => cells
+                .iter()
+                .map(|cell| match cell.size {
+                    Some(Size::Cells(length)) => length,
+                    _ => cell.block.height(),
+                })
+                .fold(0u16, |total, height| total.saturating_add(height)),
+        }
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, structure: &Structure, root: Block) -> Vec<Hit> {
+    let placement = place(area, structure, root.width(), root.height());
+    let mut hits = Vec::new();
+    draw_block(f, root, placement.area, placement.alignment, &mut hits);
+    hits
+}
+
+fn draw_block(f: &mut Frame, block: Block, area: Rect, alignment: Alignment, hits: &mut Vec<Hit>) {
+    match block {
+        Block::Lines(content) => {
+            for (offset, target) in content.targets {
+                let (Some(line), Ok(offset)) = (content.lines.get(offset), u16::try_from(offset)) else {
+                    continue;
+                };
+                if offset >= area.height {
+                    continue;
+                }
+                let width = (line.width().min(u16::MAX as usize) as u16).min(area.width);
+                let x = match alignment {
+                    Alignment::Left => area.x,
+                    Alignment::Center => area.x + (area.width - width) / 2,
+                    Alignment::Right => area.x + area.width - width,
+                };
+                hits.push(Hit {
+                    area: Rect::new(x, area.y + offset, width, 1),
+                    target,
+                });
+            }
+            f.render_widget(Paragraph::new(content.lines).alignment(alignment), area);
+        }
+        Block::Row(cells) => {
+            let constraints: Vec<Constraint> = cells
+                .iter()
+                .map(|cell| constraint(cell.size.as_ref(), Constraint::Fill(1)))
+                .collect();
+            draw_cells(f, cells, area, alignment, Direction::Horizontal, constraints, hits);
+        }
+        Block::Column(cells) => {
+            let constraints: Vec<Constraint> = cells
+                .iter()
+                .map(|cell| constraint(cell.size.as_ref(), Constraint::Length(cell.block.height())))
+                .collect();
+            draw_cells(f, cells, area, alignment, Direction::Vertical, constraints, hits);
+        }
+    }
+}
+
+fn draw_cells(
+    f: &mut Frame,
+    cells: Vec<Cell>,
+    area: Rect,
+    alignment: Alignment,
+    direction: Direction,
+    constraints: Vec<Constraint>,
+    hits: &mut Vec<Hit>,
+) {
+    let areas = Layout::default()
+        .direction(direction)
+        .flex(Flex::Start)
+        .constraints(constraints)
+        .split(area);
+
+    for (cell, cell_area) in cells.into_iter().zip(areas.iter()) {
+        let cell_alignment = cell.align.as_ref().map(alignment_for).unwrap_or(alignment);
+        draw_block(f, cell.block, *cell_area, cell_alignment, hits);
+    }
+}
+
+fn constraint(size: Option<&Size>, fallback: Constraint) -> Constraint {
+    match size {
+        Some(Size::Cells(length)) => Constraint::Length(*length),
+        Some(Size::Spec(spec)) => parse_size(spec.trim()).unwrap_or(fallback),
+        None => fallback,
+    }
+}
+
+fn parse_size(spec: &str) -> Option<Constraint> {
+    if let Some(percent) = spec.strip_suffix('%') {
+        percent.trim().parse().ok().map(Constraint::Percentage)
+    } else if let Some(weight) = spec.strip_suffix("fr") {
+        weight.trim().parse().ok().map(Constraint::Fill)
+    } else if let Some((num, den)) = spec.split_once('/') {
+        Some(Constraint::Ratio(num.trim().parse().ok()?, den.trim().parse().ok()?))
+    } else {
+        spec.parse().ok().map(Constraint::Length)
+    }
+}
+
+pub fn place(area: Rect, structure: &Structure, content_width: u16, content_height: u16) -> Placement {
+    let outer = inset(area, structure.margin);
+    let padding = structure.padding.saturating_mul(2);
+
+    let max_width = structure
+        .max_width
+        .map(|w| w.saturating_add(padding))
+        .unwrap_or(outer.width)
+        .min(outer.width);
+    let width = content_width.saturating_add(padding).min(max_width);
+    let height = content_height.saturating_add(padding).min(outer.height);
+
+    let x = match structure.position {
+        Position::Left => outer.x,
+        Position::Center => outer.x + (outer.width - width) / 2,
+        Position::Right => outer.x + outer.width - width,
+    };
+    let y = match structure.vertical {
+        VerticalPosition::Top => outer.y,
+        VerticalPosition::Middle => outer.y + (outer.height - height) / 2,
+        VerticalPosition::Bottom => outer.y + outer.height - height,
+    };
+
+    Placement {
+        area: inset(Rect::new(x, y, width, height), structure.padding),
+        alignment: alignment_for(&structure.position),
+    }
+}
+
+pub fn alignment_for(position: &Position) -> Alignment {
+    match position {
+        Position::Left => Alignment::Left,
+        Position::Center => Alignment::Center,
+        Position::Right => Alignment::Right,
+    }
+}
+
+fn inset(area: Rect, amount: u16) -> Rect {
+    area.inner(Margin {
+        horizontal: amount,
+        vertical: amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    const SCREEN: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 80,
+        height: 24,
+    };
+
+    fn structure(position: Position, vertical: VerticalPosition) -> Structure {
+        Structure {
+            position,
+            vertical,
+            ..Config::default().structure
+        }
+    }
+
+    #[test]
+    fn places_horizontally() {
+        let cases = [
+            (Position::Left, 0, Alignment::Left),
+            (Position::Center, 30, Alignment::Center),
+            (Position::Right, 60, Alignment::Right),
+        ];
+        for (position, x, alignment) in cases {
+            let placement = place(SCREEN, &structure(position, VerticalPosition::Top), 20, 10);
+            assert_eq!(placement.area, Rect::new(x, 0, 20, 10));
+            assert_eq!(placement.alignment, alignment);
+        }
+    }
+
+    #[test]
+    fn places_vertically() {
+        let cases = [
+            (VerticalPosition::Top, 0),
+            (VerticalPosition::Middle, 7),
+            (VerticalPosition::Bottom, 14),
+        ];
+        for (vertical, y) in cases {
+            let placement = place(SCREEN, &structure(Position::Left, vertical), 20, 10);
+            assert_eq!(placement.area, Rect::new(0, y, 20, 10));
+        }
+    }
+
+    #[test]
+    fn max_width_applies_to_the_content_inside_the_padding() {
+        let mut structure = structure(Position::Center, VerticalPosition::Top);
+        structure.max_width = Some(30);
+        structure.padding = 2;
+        let placement = place(SCREEN, &structure, 50, 10);
+        assert_eq!(placement.area, Rect::new(25, 2, 30, 10));
+
+        structure.margin = 1;
+        let placement = place(SCREEN, &structure, 10, 10);
+        assert_eq!(placement.area, Rect::new(35, 3, 10, 10));
+    }
+
+    #[test]
+    fn content_larger_than_the_area_is_clamped() {
+        let placement = place(SCREEN, &structure(Position::Right, VerticalPosition::Bottom), 200, 100);
+        assert_eq!(placement.area, SCREEN);
+    }
+
+    #[test]
+    fn huge_margin_does_not_panic() {
+        for (position, vertical) in [
+            (Position::Right, VerticalPosition::Bottom),
+            (Position::Center, VerticalPosition::Middle),
+        ] {
+            let mut structure = structure(position, vertical);
+            structure.margin = 50;
+            structure.padding = 3;
+            let placement = place(SCREEN, &structure, 20, 10);
+            assert!(placement.area.is_empty());
+        }
+    }
+
+    #[test]
+    fn parses_size_specs() {
+        assert_eq!(parse_size("30%"), Some(Constraint::Percentage(30)));
+        assert_eq!(parse_size("2fr"), Some(Constraint::Fill(2)));
+        assert_eq!(parse_size("1 / 3"), Some(Constraint::Ratio(1, 3)));
+        assert_eq!(parse_size("12"), Some(Constraint::Length(12)));
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        for spec in ["", "%", "abc", "x%", "fr", "1/", "/3", "-5", "1.5fr"] {
+            assert_eq!(parse_size(spec), None, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn invalid_size_falls_back() {
+        let fallback = Constraint::Fill(1);
+        assert_eq!(constraint(None, fallback), fallback);
+        assert_eq!(constraint(Some(&Size::Cells(4)), fallback), Constraint::Length(4));
+        assert_eq!(constraint(Some(&Size::Spec(" 50% ".to_string())), fallback), Constraint::Percentage(50));
+        assert_eq!(constraint(Some(&Size::Spec("half".to_string())), fallback), fallback);
+    }
+}
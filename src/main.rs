@@ -1,20 +1,39 @@
+mod ansi;
+mod cli;
 mod config;
+mod dialog;
+mod exec;
+mod expand;
+mod keys;
+mod layout;
+mod prompt;
+mod output;
+mod search;
+mod theme;
+mod validate;
+mod watch;
 
 use config::Config;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::Alignment,
+    layout::{Position, Rect},
     style::{Color, Modifier, Style},
+    symbols::border,
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Paragraph, Wrap},
     Frame, Terminal,
 };
+use std::collections::{HashMap, HashSet};
 use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use chrono::Local;
 use std::io::Write;
 use rand::seq::SliceRandom;
@@ -36,6 +55,9 @@ const DOTT_LOGO: &str = r#"
                       
 "#;
 
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
+const PAGE_SIZE: usize = 5;
+
 fn display_kitty_image(path: &str) -> Result<(), String> {
     use std::fs;
     
@@ -112,57 +134,436 @@ struct App {
     selected: usize,
     config: Config,
     all_entries: Vec<(String, config::MenuItem)>,
+    collapsed: HashSet<String>,
+    filter: Option<String>,
+    hotkeys: Vec<keys::Hotkey>,
+    warnings: Vec<String>,
+    hits: Vec<layout::Hit>,
+    theme: theme::Theme,
+    theme_preview: Option<String>,
+    notice: Option<(String, Instant)>,
+    status: Option<exec::Report>,
+    jobs: Vec<exec::Job>,
+    output: Option<output::OutputPane>,
+    confirm: Option<dialog::Confirm>,
+    prompt: Option<dialog::Prompt>,
+    watcher: watch::Watcher,
+    config_error: Option<config::ConfigError>,
+    bindings: keys::Bindings,
 }
 
 impl App {
     fn new() -> App {
-        let config = Config::load();
+        let mut app = App::with_config(Config::load());
+        if !Config::config_path().exists() {
+            app.notice = Some(("No config file yet, run `dott-tui init` to create one".to_string(), Instant::now()));
+        }
+        app
+    }
+
+    fn with_config(loaded: Result<Config, config::ConfigError>) -> App {
+        let (config, config_error) = match loaded {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(e)),
+        };
+        let mut app = App {
+            selected: 0,
+            watcher: watch::Watcher::new(&config),
+            config,
+            config_error,
+            all_entries: Vec::new(),
+            collapsed: HashSet::new(),
+            filter: None,
+            hotkeys: Vec::new(),
+            warnings: Vec::new(),
+            hits: Vec::new(),
+            theme: theme::Theme::default(),
+            theme_preview: None,
+            notice: None,
+            status: None,
+            jobs: Vec::new(),
+            output: None,
+            confirm: None,
+            prompt: None,
+            bindings: keys::Bindings::default(),
+        };
+        app.apply_config();
+        app
+    }
+
+    fn apply_config(&mut self) {
+        self.all_entries = self.config.get_placed_entries();
+        self.collapsed = self
+            .all_entries
+            .iter()
+            .map(|(group, _)| group)
+            .filter(|group| self.collapsed_by_config(group))
+            .cloned()
+            .collect();
+
+        let mut warnings = validate::collect(&self.config);
+        let (bindings, _) = keys::Bindings::from_config(&self.config.keys);
+        let (hotkeys, _) = keys::assign_hotkeys(&self.all_entries, &self.config.hotkeys, &bindings);
+        let theme = match self.theme_preview {
+            Some(ref name) => {
+                let (theme, theme_warnings) = theme::Theme::named(name, &Config::themes_dir());
+                warnings.extend(theme_warnings);
+                theme
+            }
+            None => theme::Theme::from_config(&self.config.theme, &Config::themes_dir()).0,
+        };
+        self.bindings = bindings;
+        self.hotkeys = hotkeys;
+        self.theme = theme;
+        self.warnings = warnings;
+    }
+
+    fn reload_config(&mut self) {
+        self.reload_with(Config::load());
+    }
+
+    fn reload_with(&mut self, loaded: Result<Config, config::ConfigError>) {
+        let previous = self
+            .all_entries
+            .get(self.selected)
+            .map(|(group, entry)| (group.clone(), entry.name.clone()));
+        let previous_groups: HashMap<String, (bool, bool)> = self
+            .all_entries
+            .iter()
+            .map(|(group, _)| (group.clone(), (self.collapsed_by_config(group), self.collapsed.contains(group))))
+            .collect();
         
-        let mut all_entries = Vec::new();
-        for module in config.get_ordered_modules() {
-            if let config::ModuleType::Entries(group_name) = module.module_type {
-                let entries = config.get_entries(&group_name);
-                for entry in entries {
-                    all_entries.push((group_name.clone(), entry.clone()));
+        match loaded {
+            Ok(config) => {
+                if config.theme != self.config.theme {
+                    self.theme_preview = None;
+                }
+                self.config = config;
+                self.config_error = None;
+            }
+            Err(e) => {
+                self.config_error = Some(e);
+                self.watcher = watch::Watcher::new(&self.config);
+                return;
+            }
+        }
+        self.apply_config();
+        self.watcher = watch::Watcher::new(&self.config);
+
+        for (group, (was_configured, was_collapsed)) in previous_groups {
+            let still_shown = self.all_entries.iter().any(|(g, _)| *g == group);
+            if still_shown && self.collapsed_by_config(&group) == was_configured {
+                if was_collapsed {
+                    self.collapsed.insert(group);
+                } else {
+                    self.collapsed.remove(&group);
                 }
             }
         }
         
-        App {
-            selected: 0,
-            config,
-            all_entries,
+        let same_entry = previous.and_then(|(group, name)| {
+            self.all_entries
+                .iter()
+                .position(|(g, entry)| *g == group && entry.name == name)
+                .or_else(|| self.all_entries.iter().position(|(_, entry)| entry.name == name))
+        });
+        self.selected = same_entry.unwrap_or_else(|| self.selected.min(self.all_entries.len().saturating_sub(1)));
+    }
+
+    fn collapsed_by_config(&self, group: &str) -> bool {
+        self.config.get_group(group).is_some_and(|group| group.collapsed)
+    }
+
+    fn poll_jobs(&mut self) {
+        let mut index = 0;
+        while index < self.jobs.len() {
+            match self.jobs[index].poll() {
+                Some(report) => {
+                    self.jobs.remove(index);
+                    if self.config.launch.show_success || !report.success {
+                        self.status = Some(report);
+                    }
+                }
+                None => index += 1,
+            }
         }
     }
 
+    fn is_running(&self, group: &str, name: &str) -> bool {
+        self.jobs.iter().any(|job| job.group == group && job.name == name)
+    }
+
+    fn cycle_theme(&mut self) {
+        let themes_dir = Config::themes_dir();
+        let names: Vec<String> = theme::available_themes(&themes_dir)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let current = self
+            .theme_preview
+            .clone()
+            .or_else(|| self.config.theme.name.clone())
+            .unwrap_or_else(|| "default".to_string());
+        let next = names
+            .iter()
+            .position(|name| *name == current)
+            .map(|position| (position + 1) % names.len())
+            .unwrap_or(0);
+        
+        let Some(name) = names.get(next).cloned() else {
+            return;
+        };
+        let (theme, warnings) = theme::Theme::named(&name, &themes_dir);
+        self.theme = theme;
+        self.notice = Some((format!("Theme: {}", name), Instant::now()));
+        self.warnings.extend(warnings);
+        self.theme_preview = Some(name);
+    }
+
+    fn hotkey_for(&self, key: &KeyEvent) -> Option<usize> {
+        self.hotkeys.iter().find(|h| h.chord.matches(key)).map(|h| h.index)
+    }
+
+    fn hotkey_label(&self, index: usize) -> Option<String> {
+        self.hotkeys.iter().find(|h| h.index == index).map(|h| h.chord.to_string())
+    }
+
+    fn stops(&self) -> Vec<usize> {
+        if self.filter.is_some() {
+            return self.matches().into_iter().map(|(index, _)| index).collect();
+        }
+        
+        let mut stops = Vec::new();
+        for (index, (group_name, _)) in self.all_entries.iter().enumerate() {
+            let starts_group = index == 0 || self.all_entries[index - 1].0 != *group_name;
+            if !self.collapsed.contains(group_name) || starts_group {
+                stops.push(index);
+            }
+        }
+        stops
+    }
+
     fn next(&mut self) {
-        if !self.all_entries.is_empty() {
-            self.selected = (self.selected + 1) % self.all_entries.len();
+        let stops = self.stops();
+        if !stops.is_empty() {
+            let position = stops.iter().position(|&stop| stop > self.selected).unwrap_or(0);
+            self.selected = stops[position];
         }
     }
 
     fn previous(&mut self) {
-        if !self.all_entries.is_empty() {
-            if self.selected > 0 {
-                self.selected -= 1;
+        let stops = self.stops();
+        if !stops.is_empty() {
+            let position = stops
+                .iter()
+                .rposition(|&stop| stop < self.selected)
+                .unwrap_or(stops.len() - 1);
+            self.selected = stops[position];
+        }
+    }
+
+    fn matches(&self) -> Vec<(usize, search::FuzzyMatch)> {
+        let Some(ref filter) = self.filter else {
+            return Vec::new();
+        };
+        self.all_entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, entry))| {
+                search::match_entry(filter, entry, &self.config.search).map(|found| (index, found))
+            })
+            .collect()
+    }
+
+    fn is_match(&self, index: usize) -> bool {
+        self.matches().iter().any(|(i, _)| *i == index)
+    }
+
+    fn open_filter(&mut self) {
+        self.filter = Some(String::new());
+    }
+
+    fn close_filter(&mut self) {
+        self.filter = None;
+    }
+
+    fn push_filter_char(&mut self, c: char) {
+        if let Some(ref mut filter) = self.filter {
+            filter.push(c);
+        }
+        self.select_top_match();
+    }
+
+    fn pop_filter_char(&mut self) {
+        if let Some(ref mut filter) = self.filter {
+            filter.pop();
+        }
+        self.select_top_match();
+    }
+
+    fn select_top_match(&mut self) {
+        let top = self
+            .matches()
+            .into_iter()
+            .max_by(|(a_index, a), (b_index, b)| a.score.cmp(&b.score).then(b_index.cmp(a_index)));
+        if let Some((index, _)) = top {
+            self.selected = index;
+        }
+    }
+
+    fn select_first(&mut self) {
+        if let Some(&first) = self.stops().first() {
+            self.selected = first;
+        }
+    }
+
+    fn select_last(&mut self) {
+        if let Some(&last) = self.stops().last() {
+            self.selected = last;
+        }
+    }
+
+    fn page(&mut self, forward: bool) {
+        for _ in 0..PAGE_SIZE {
+            let before = self.selected;
+            if forward {
+                self.next();
+                if self.selected < before {
+                    self.selected = before;
+                    break;
+                }
             } else {
-                self.selected = self.all_entries.len() - 1;
+                self.previous();
+                if self.selected > before {
+                    self.selected = before;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn selected_group_collapsed(&self) -> bool {
+        if self.filter.is_some() {
+            return false;
+        }
+        self.all_entries
+            .get(self.selected)
+            .is_some_and(|(group_name, _)| self.collapsed.contains(group_name))
+    }
+
+    fn hit_at(&self, column: u16, row: u16) -> Option<layout::Target> {
+        self.hits
+            .iter()
+            .find(|hit| hit.area.contains(Position::new(column, row)))
+            .map(|hit| hit.target)
+    }
+
+    fn toggle_group_at(&mut self, index: usize) {
+        let in_group = self.all_entries.get(index).map(|(group_name, _)| group_name)
+            == self.all_entries.get(self.selected).map(|(group_name, _)| group_name);
+        if !in_group {
+            self.selected = index;
+        }
+        self.toggle_selected_group();
+    }
+
+    fn toggle_selected_group(&mut self) {
+        if let Some((group_name, _)) = self.all_entries.get(self.selected) {
+            let group_name = group_name.clone();
+            if !self.collapsed.remove(&group_name) {
+                while self.selected > 0 && self.all_entries[self.selected - 1].0 == group_name {
+                    self.selected -= 1;
+                }
+                self.collapsed.insert(group_name);
             }
         }
     }
 
     fn get_selected_item(&self) -> Option<&config::MenuItem> {
+        if self.selected_group_collapsed() {
+            return None;
+        }
+        if self.filter.is_some() && !self.is_match(self.selected) {
+            return None;
+        }
         self.all_entries.get(self.selected).map(|(_, item)| item)
     }
 }
 
 fn main() -> Result<(), io::Error> {
+    let args = match cli::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("dott-tui: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Some(path) = args.config {
+        Config::set_config_path(path);
+    }
+    if let Some(ref profile) = args.profile {
+        Config::set_profile(profile);
+    }
+
+    match args.command {
+        cli::Command::Run => {}
+        cli::Command::Init { force } => {
+            let path = Config::config_path();
+            if path.exists() && !force {
+                eprintln!("dott-tui: {} already exists, use `init --force` to overwrite it", path.display());
+                std::process::exit(1);
+            }
+            if let Err(e) = Config::write_template(&path) {
+                eprintln!("dott-tui: could not write {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            println!("Wrote {}", path.display());
+            return Ok(());
+        }
+        cli::Command::Check => {
+            let ok = check_config(&Config::config_path());
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        cli::Command::ListThemes => {
+            for (name, from_user) in theme::available_themes(&Config::themes_dir()) {
+                if from_user {
+                    println!("{} (user)", name);
+                } else {
+                    println!("{}", name);
+                }
+            }
+            return Ok(());
+        }
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        cli::Command::Version => {
+            println!("dott-tui {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+    }
+
+    let config_path = Config::config_path();
+    if !config_path.exists() && !Config::is_default_path() {
+        match args.profile {
+            Some(profile) => {
+                let available = config::available_profiles();
+                eprintln!("dott-tui: profile \"{}\" not found at {}", profile, config_path.display());
+                if !available.is_empty() {
+                    eprintln!("available profiles: {}", available.join(", "));
+                }
+            }
+            None => eprintln!("dott-tui: config file {} does not exist", config_path.display()),
+        }
+        std::process::exit(1);
+    }
+
     let app = App::new();
     
     if let config::LogoType::Image = app.config.logo_type {
         if let Some(ref image_path) = app.config.image_logo_path {
-            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-            let expanded_path = image_path.replace("~", &home);
+            let expanded_path = expand::expand(image_path);
             
             println!("\n");
             if let Err(e) = display_kitty_image(&expanded_path) {
@@ -177,7 +578,8 @@ fn main() -> Result<(), io::Error> {
     
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    set_mouse_capture(&mut stdout, app.config.mouse.enabled)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -199,256 +601,737 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
+fn check_config(path: &Path) -> bool {
+    let config = match Config::load_from(path) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}", e);
+            println!("\n1 problem found");
+            return false;
+        }
+    };
+
+    let mut problems = validate::collect(&config);
+    problems.extend(validate::missing_files(&config));
+
+    for problem in &problems {
+        println!("{}: {}", path.display(), problem);
+    }
+    match problems.len() {
+        0 => println!("{}: ok", path.display()),
+        1 => println!("\n1 problem found"),
+        count => println!("\n{} problems found", count),
+    }
+    problems.is_empty()
+}
+
 fn run_app<B: ratatui::backend::Backend + std::io::Write>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> io::Result<()> {
+    let mut mouse_captured = app.config.mouse.enabled;
     loop {
+        if app.config.auto_reload && app.watcher.changed() {
+            app.reload_config();
+            if app.config_error.is_none() {
+                app.notice = Some(("Config reloaded".to_string(), Instant::now()));
+            }
+        }
+        if app.config.mouse.enabled != mouse_captured {
+            mouse_captured = app.config.mouse.enabled;
+            set_mouse_capture(terminal.backend_mut(), mouse_captured)?;
+        }
+        app.poll_jobs();
+        if let Some(ref mut pane) = app.output {
+            pane.poll();
+        }
         terminal.draw(|f| ui(f, app))?;
 
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                    KeyCode::Down | KeyCode::Char('j') => app.next(),
-                    KeyCode::Up | KeyCode::Char('k') => app.previous(),
-                    KeyCode::Char('u') => {
-                        app.config = Config::load();
-                        app.selected = 0;
-                        
-                        app.all_entries.clear();
-                        for module in app.config.get_ordered_modules() {
-                            if let config::ModuleType::Entries(group_name) = module.module_type {
-                                let entries = app.config.get_entries(&group_name);
-                                for entry in entries {
-                                    app.all_entries.push((group_name.clone(), entry.clone()));
+            let event = event::read()?;
+            if let Some(ref mut prompt) = app.prompt {
+                if let Event::Key(key) = event {
+                    match key.code {
+                        KeyCode::Esc => app.prompt = None,
+                        KeyCode::Enter => {
+                            if let Some(args) = prompt.submit() {
+                                let entry = prompt.entry.clone();
+                                app.prompt = None;
+                                if confirm_entry(terminal, app, &entry, args)? {
+                                    return Ok(());
                                 }
                             }
                         }
-                        
-                        terminal.clear()?;
+                        KeyCode::Up | KeyCode::BackTab if prompt.is_choice() => prompt.move_choice(false),
+                        KeyCode::Down | KeyCode::Tab if prompt.is_choice() => prompt.move_choice(true),
+                        KeyCode::Char('k') if prompt.is_choice() => prompt.move_choice(false),
+                        KeyCode::Char('j') if prompt.is_choice() => prompt.move_choice(true),
+                        KeyCode::Backspace => prompt.pop_char(),
+                        KeyCode::Char(c) if !prompt.is_choice() => prompt.push_char(c),
+                        _ => {}
                     }
-                    KeyCode::Enter => {
-                    if let Some(selected) = app.get_selected_item() {
-                    
-                    match selected.name.as_str() {
-                        "Quit" => return Ok(()),
-                        "View Shell" => {
-                            if let Some(shell_config) = detect_shell_config() {
-                                disable_raw_mode()?;
-                                execute!(
-                                    terminal.backend_mut(),
-                                    LeaveAlternateScreen,
-                                    DisableMouseCapture
-                                )?;
-                                terminal.show_cursor()?;
-
-                                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                                let expanded_path = shell_config.replace("~", &home);
-
-                                let _ = std::process::Command::new("nvim")
-                                    .arg(&expanded_path)
-                                    .status();
-
-                                enable_raw_mode()?;
-                                execute!(
-                                    terminal.backend_mut(),
-                                    EnterAlternateScreen,
-                                    EnableMouseCapture
-                                )?;
-                                terminal.clear()?;
-                            }
+                }
+                continue;
+            }
+            
+            if let Some(ref mut confirm) = app.confirm {
+                if let Event::Key(key) = event {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => confirm.yes = true,
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') | KeyCode::Esc => {
+                            app.confirm = None;
+                            continue;
+                        }
+                        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab
+                        | KeyCode::Char('h') | KeyCode::Char('l') => {
+                            confirm.yes = !confirm.yes;
+                            continue;
+                        }
+                        KeyCode::Enter => {}
+                        _ => continue,
+                    }
+                    if let Some(confirm) = app.confirm.take() {
+                        if confirm.yes && execute_entry(terminal, app, &confirm.entry, &confirm.args)? {
+                            return Ok(());
                         }
-                        "Edit Dott Config" => {
-                            disable_raw_mode()?;
-                            execute!(
-                                terminal.backend_mut(),
-                                LeaveAlternateScreen,
-                                DisableMouseCapture
-                            )?;
-                            terminal.show_cursor()?;
-
-                            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                            let config_path = format!("{}/.config/dott/config.toml", home);
-                            let _ = std::process::Command::new("nvim")
-                                .arg(&config_path)
-                                .status();
-
-                            enable_raw_mode()?;
-                            execute!(
-                                terminal.backend_mut(),
-                                EnterAlternateScreen,
-                                EnableMouseCapture
-                            )?;
-                            terminal.clear()?;
+                    }
+                }
+                continue;
+            }
+            
+            if let Some(ref mut pane) = app.output {
+                match event {
+                    Event::Key(key) => match (key.code, app.bindings.action_for(&key)) {
+                        (KeyCode::Esc, _) => app.output = None,
+                        (_, Some(keys::Action::Quit | keys::Action::Launch)) => app.output = None,
+                        (_, Some(keys::Action::Up)) => pane.scroll_by(-1),
+                        (_, Some(keys::Action::Down)) => pane.scroll_by(1),
+                        (_, Some(keys::Action::PageUp)) => pane.page(false),
+                        (_, Some(keys::Action::PageDown)) => pane.page(true),
+                        (_, Some(keys::Action::First)) => pane.scroll_to_top(),
+                        (_, Some(keys::Action::Last)) => pane.scroll_to_bottom(),
+                        _ => {}
+                    },
+                    Event::Mouse(mouse) if app.config.mouse.enabled => match mouse.kind {
+                        MouseEventKind::ScrollDown => pane.scroll_by(3),
+                        MouseEventKind::ScrollUp => pane.scroll_by(-3),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+                continue;
+            }
+            
+            if let Event::Mouse(mouse) = event {
+                if !app.config.mouse.enabled {
+                    continue;
+                }
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => app.next(),
+                    MouseEventKind::ScrollUp => app.previous(),
+                    MouseEventKind::Moved if app.config.mouse.hover => {
+                        if let Some(layout::Target::Entry(index)) = app.hit_at(mouse.column, mouse.row) {
+                            app.selected = index;
+                        }
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => match app.hit_at(mouse.column, mouse.row) {
+                        Some(layout::Target::Entry(index)) => {
+                            app.selected = index;
+                            if let Some(entry) = app.get_selected_item().cloned() {
+                                app.close_filter();
+                                if launch_entry(terminal, app, &entry)? {
+                                    return Ok(());
+                                }
+                            }
                         }
-                        _ => {
-                            if !selected.command.is_empty() {
-                                disable_raw_mode()?;
-                                execute!(
-                                    terminal.backend_mut(),
-                                    LeaveAlternateScreen,
-                                    DisableMouseCapture
-                                )?;
-                                terminal.show_cursor()?;
-
-                                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                                let expanded_args: Vec<String> = selected.args.iter()
-                                    .map(|arg| arg.replace("~", &home))
-                                    .collect();
-                                let args: Vec<&str> = expanded_args.iter().map(|s| s.as_str()).collect();
-                                
-                                let _ = std::process::Command::new(&selected.command)
-                                    .args(&args)
-                                    .status();
-
-                                enable_raw_mode()?;
-                                execute!(
-                                    terminal.backend_mut(),
-                                    EnterAlternateScreen,
-                                    EnableMouseCapture
-                                )?;
-                                terminal.clear()?;
+                        Some(layout::Target::Group(index)) => app.toggle_group_at(index),
+                        None => {}
+                    },
+                    _ => {}
+                }
+                continue;
+            }
+            
+            if let Event::Key(key) = event {
+                app.status = None;
+                
+                if app.filter.is_some() {
+                    match key.code {
+                        KeyCode::Esc => app.close_filter(),
+                        KeyCode::Enter => {
+                            let quit = match app.get_selected_item().cloned() {
+                                Some(entry) => launch_entry(terminal, app, &entry)?,
+                                None => false,
+                            };
+                            app.close_filter();
+                            if quit {
+                                return Ok(());
                             }
                         }
+                        KeyCode::Backspace => app.pop_filter_char(),
+                        KeyCode::Down => app.next(),
+                        KeyCode::Up => app.previous(),
+                        KeyCode::Char(c) => app.push_filter_char(c),
+                        _ => {}
                     }
+                    continue;
                 }
+                
+                if let Some(index) = app.hotkey_for(&key) {
+                    app.selected = index;
+                    let entry = app.all_entries[index].1.clone();
+                    if launch_entry(terminal, app, &entry)? {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                
+                match app.bindings.action_for(&key) {
+                    Some(keys::Action::Quit) => return Ok(()),
+                    Some(keys::Action::Down) => app.next(),
+                    Some(keys::Action::Up) => app.previous(),
+                    Some(keys::Action::First) => app.select_first(),
+                    Some(keys::Action::Last) => app.select_last(),
+                    Some(keys::Action::PageDown) => app.page(true),
+                    Some(keys::Action::PageUp) => app.page(false),
+                    Some(keys::Action::Reload) => {
+                        app.reload_config();
+                        terminal.clear()?;
+                    }
+                    Some(keys::Action::ToggleGroup) => app.toggle_selected_group(),
+                    Some(keys::Action::Search) => app.open_filter(),
+                    Some(keys::Action::CycleTheme) => app.cycle_theme(),
+                    Some(keys::Action::Launch) => {
+                        if app.selected_group_collapsed() {
+                            app.toggle_selected_group();
+                        } else if let Some(entry) = app.get_selected_item().cloned() {
+                            if launch_entry(terminal, app, &entry)? {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    None => {}
                 }
-                _ => {}
             }
+        }
+    }
+}
+
+fn launch_entry<B: ratatui::backend::Backend + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    selected: &config::MenuItem,
+) -> io::Result<bool> {
+    let placeholders = prompt::placeholders(&selected.args);
+    if !placeholders.is_empty() {
+        app.prompt = Some(dialog::Prompt::new(selected, placeholders));
+        return Ok(false);
+    }
+    confirm_entry(terminal, app, selected, exec::expand_args(selected))
+}
+
+fn confirm_entry<B: ratatui::backend::Backend + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    selected: &config::MenuItem,
+    args: Vec<String>,
+) -> io::Result<bool> {
+    if let Some(message) = selected.confirm_message() {
+        app.confirm = Some(dialog::Confirm::new(selected, args, message));
+        return Ok(false);
+    }
+    execute_entry(terminal, app, selected, &args)
+}
+
+fn execute_entry<B: ratatui::backend::Backend + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    selected: &config::MenuItem,
+    args: &[String],
+) -> io::Result<bool> {
+    let report = match selected.builtin() {
+        Some(config::EntryAction::Quit) => return Ok(true),
+        Some(config::EntryAction::Reload) => {
+            app.reload_config();
+            terminal.clear()?;
+            return Ok(false);
+        }
+        Some(config::EntryAction::Theme) => {
+            app.cycle_theme();
+            return Ok(false);
+        }
+        Some(config::EntryAction::Search) => {
+            app.open_filter();
+            return Ok(false);
+        }
+        Some(config::EntryAction::EditShellConfig) => {
+            let Some(shell_config) = detect_shell_config() else {
+                return Ok(false);
+            };
+            suspend_terminal(terminal)?;
+
+            let expanded_path = expand::expand(&shell_config);
+            exec::run(
+                &mut exec::editor_command(app.config.editor.as_deref(), Path::new(&expanded_path)),
+                &selected.name,
+                false,
+            )
+        }
+        Some(config::EntryAction::EditConfig) => {
+            let config_path = Config::config_path();
+            if !config_path.exists() {
+                app.notice = Some(("No config file yet, run `dott-tui init` to create one".to_string(), Instant::now()));
+                return Ok(false);
+            }
+            suspend_terminal(terminal)?;
+
+            let report = exec::run(
+                &mut exec::editor_command(app.config.editor.as_deref(), &config_path),
+                &selected.name,
+                false,
+            );
+            app.reload_config();
+            report
+        }
+        _ if selected.command.is_empty() => return Ok(false),
+        _ if selected.mode == config::LaunchMode::Capture => {
+            match exec::capture_entry(selected, args) {
+                Ok(capture) => app.output = Some(output::OutputPane::new(&selected.name, capture)),
+                Err(report) => app.status = Some(report),
+            }
+            return Ok(false);
+        }
+        _ if selected.mode != config::LaunchMode::Foreground => {
+            let group = app
+                .all_entries
+                .get(app.selected)
+                .map(|(group, _)| group.clone())
+                .unwrap_or_default();
+            let report = match exec::spawn_entry(selected, args, &group) {
+                Ok(job) => {
+                    app.jobs.extend(job);
+                    exec::started(selected)
+                }
+                Err(report) => report,
+            };
+            if app.config.launch.show_success || !report.success {
+                app.status = Some(report);
             }
+            return Ok(false);
+        }
+        _ => {
+            suspend_terminal(terminal)?;
+            exec::run_entry(selected, args)
         }
+    };
+
+    let pause = selected.pause.unwrap_or(app.config.launch.pause);
+    if exec::should_pause(pause, &report) {
+        exec::wait_for_key(&report)?;
     }
+    resume_terminal(terminal, app.config.mouse.enabled)?;
+
+    if app.config.launch.show_success || !report.success {
+        app.status = Some(report);
+    }
+    
+    Ok(false)
 }
 
-fn ui(f: &mut Frame, app: &App) {
-    let size = f.area();
+fn suspend_terminal<B: ratatui::backend::Backend + std::io::Write>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()
+}
+
+fn resume_terminal<B: ratatui::backend::Backend + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    mouse: bool,
+) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    set_mouse_capture(terminal.backend_mut(), mouse)?;
+    terminal.clear()
+}
+
+fn set_mouse_capture<W: std::io::Write>(writer: &mut W, enabled: bool) -> io::Result<()> {
+    if enabled {
+        execute!(writer, EnableMouseCapture)
+    } else {
+        execute!(writer, DisableMouseCapture)
+    }
+}
+
+fn ui(f: &mut Frame, app: &mut App) {
+    let mut size = f.area();
+    
+    if let Some(ref error) = app.config_error {
+        let text = format!("✗ Config error: {}", error);
+        let length = text.chars().count() as u16;
+        let rows = if length <= size.width { 1 } else { length.div_ceil(size.width.max(1)) + 1 };
+        let rows = rows.min(3).min(size.height);
+        let area = Rect::new(size.x, size.y, size.width, rows);
+        f.render_widget(
+            Paragraph::new(Span::styled(text, app.theme.warning.add_modifier(Modifier::BOLD))).wrap(Wrap { trim: false }),
+            area,
+        );
+        size.y += rows;
+        size.height -= rows;
+    }
+    
+    if app.filter.is_some() && size.height > 0 {
+        size.height -= 1;
+        let bar = Rect::new(size.x, size.y + size.height, size.width, 1);
+        f.render_widget(Paragraph::new(render_filter_bar(app)), bar);
+    }
+    
+    if let Some(ref status) = app.status {
+        if (!status.success || status.finished.elapsed() < NOTICE_DURATION) && size.height > 0 {
+            size.height -= 1;
+            let area = Rect::new(size.x, size.y + size.height, size.width, 1);
+            let (symbol, style) = if status.success {
+                ("✓", app.theme.accent)
+            } else {
+                ("✗", app.theme.warning)
+            };
+            let line = Span::styled(format!("{} {}", symbol, status.message), style);
+            f.render_widget(Paragraph::new(line), area);
+        }
+    }
+    
+    if let Some((ref notice, shown_at)) = app.notice {
+        if shown_at.elapsed() < NOTICE_DURATION && size.height > 0 {
+            size.height -= 1;
+            let area = Rect::new(size.x, size.y + size.height, size.width, 1);
+            f.render_widget(Paragraph::new(Span::styled(notice.clone(), app.theme.accent)), area);
+        }
+    }
+    
+    let warning_rows = (app.warnings.len() as u16).min(3).min(size.height);
+    if warning_rows > 0 {
+        size.height -= warning_rows;
+        let area = Rect::new(size.x, size.y + size.height, size.width, warning_rows);
+        let lines: Vec<Line> = app
+            .warnings
+            .iter()
+            .map(|warning| Line::from(Span::styled(format!("⚠ {}", warning), app.theme.warning)))
+            .collect();
+        f.render_widget(Paragraph::new(lines), area);
+    }
+    
+    let mut entry_index = 0;
+    let root = layout::Block::build(&app.config.get_layout(), &mut |module_type| {
+        render_module(module_type, app, &mut entry_index)
+    });
+    
+    app.hits = layout::draw(f, size, &app.config.structure, root);
+    
+    if let Some(ref mut pane) = app.output {
+        output::draw(f, f.area(), pane, &app.theme);
+    }
     
-    let ordered_modules = app.config.get_ordered_modules();
+    if let Some(ref prompt) = app.prompt {
+        dialog::draw_prompt(f, f.area(), prompt, &app.theme);
+    }
     
+    if let Some(ref confirm) = app.confirm {
+        dialog::draw_confirm(f, f.area(), confirm, &app.theme);
+    }
+}
+
+fn describe_action(action: config::EntryAction) -> &'static str {
+    match action {
+        config::EntryAction::Quit => "Exit application",
+        config::EntryAction::EditConfig => "Edit dott config in your editor",
+        config::EntryAction::EditShellConfig => "View shell config in your editor",
+        config::EntryAction::Reload => "Reload config",
+        config::EntryAction::Theme => "Switch to the next theme",
+        config::EntryAction::Search => "Filter entries",
+    }
+}
+
+fn render_filter_bar(app: &App) -> Line<'static> {
+    let filter = app.filter.clone().unwrap_or_default();
+    let count = app.matches().len();
+    Line::from(vec![
+        Span::styled("/", app.theme.accent.add_modifier(Modifier::BOLD)),
+        Span::styled(filter, app.theme.entry),
+        Span::styled("▏", app.theme.accent),
+        Span::styled(format!("  {}/{}", count, app.all_entries.len()), app.theme.muted),
+    ])
+}
+
+fn render_module(module_type: &config::ModuleType, app: &App, entry_index: &mut usize) -> layout::Content {
     let mut lines = Vec::new();
-    let mut current_entry_index = 0;
-    
-    for module in &ordered_modules {
-        match &module.module_type {
-            config::ModuleType::Logo(logo_type) => {
-                let logo_text = get_logo_text_with_type(logo_type, &app.config);
-                for line in logo_text.lines() {
-                    lines.push(Line::from(Span::styled(line.to_string(), Style::default().fg(Color::Cyan))));
-                }
-            }
-            config::ModuleType::Entries(group_name) => {
-                let entries = app.config.get_entries(group_name);
-                for entry in entries {
-                    let is_selected = current_entry_index == app.selected;
-                    let (prefix, style) = if is_selected {
-                        (
-                            "> ",
-                            Style::default()
-                                .fg(Color::Black)
-                                .bg(Color::Cyan)
-                                .add_modifier(Modifier::BOLD)
-                        )
-                    } else {
-                        (
-                            "> ",
-                            Style::default().fg(Color::White)
-                        )
-                    };
-                    lines.push(Line::from(Span::styled(format!("{}{}  ", prefix, entry.name), style)));
-                    current_entry_index += 1;
-                }
-            }
-            config::ModuleType::Colors => {
-                if let Some(ref _custom) = app.config.custom {
-                    let color_lines = render_terminal_colors_lines(&app.config.custom.as_ref().unwrap().terminal_colors);
-                    lines.extend(color_lines);
-                }
+    
+    match module_type {
+        config::ModuleType::Logo(logo_type) => {
+            let logo_text = get_logo_text_with_type(logo_type, &app.config);
+            for line in logo_text.lines() {
+                lines.push(Line::from(Span::styled(line.to_string(), app.theme.logo)));
             }
-            config::ModuleType::Clock => {
-                if let Some(ref _custom) = app.config.custom {
-                    let time = Local::now().format("%H:%M:%S").to_string();
-                    lines.push(Line::from(Span::styled(time, Style::default().fg(Color::Cyan))));
-                }
+        }
+        config::ModuleType::Entries(group_name) => {
+            return render_entries(group_name, app, entry_index);
+        }
+        config::ModuleType::Colors => {
+            if let Some(ref _custom) = app.config.custom {
+                let color_lines = render_terminal_colors_lines(&app.config.custom.as_ref().unwrap().terminal_colors);
+                lines.extend(color_lines);
             }
-            config::ModuleType::Help => {
-                lines.push(Line::from(Span::styled(
-                    "↑/k: Up | ↓/j: Down | Enter: Select | u: Reload Config | q/Esc: Quit".to_string(),
-                    Style::default().fg(Color::DarkGray)
-                )));
+        }
+        config::ModuleType::Clock => {
+            if let Some(ref _custom) = app.config.custom {
+                let time = Local::now().format("%H:%M:%S").to_string();
+                lines.push(Line::from(Span::styled(time, app.theme.clock)));
             }
-            config::ModuleType::Selected => {
-                if let Some(ref _custom) = app.config.custom {
-                    if let Some(selected_entry) = app.get_selected_item() {
-                        let command_text = if selected_entry.command.is_empty() {
-                            match selected_entry.name.as_str() {
-                                "Quit" => "Exit application".to_string(),
-                                "Edit Dott Config" => "Edit dott config in nvim".to_string(),
-                                "View Shell" => "View shell config in nvim".to_string(),
-                                _ => "No command".to_string(),
-                            }
+        }
+        config::ModuleType::Help => {
+            lines.push(Line::from(Span::styled(
+                app.bindings.help_text(),
+                app.theme.muted
+            )));
+        }
+        config::ModuleType::Selected => {
+            if let Some(ref _custom) = app.config.custom {
+                if let Some(selected_entry) = app.get_selected_item() {
+                    let command_text = if let Some(action) = selected_entry.builtin() {
+                        describe_action(action).to_string()
+                    } else if selected_entry.command.is_empty() {
+                        "No command".to_string()
+                    } else {
+                        let args_str = if selected_entry.args.is_empty() {
+                            String::new()
                         } else {
-                            let args_str = if selected_entry.args.is_empty() {
-                                String::new()
-                            } else {
-                                format!(" {}", selected_entry.args.join(" "))
-                            };
-                            format!("{}{}", selected_entry.command, args_str)
+                            format!(" {}", selected_entry.args.join(" "))
                         };
-                        lines.push(Line::from(Span::styled(
-                            format!("Selected: {}", command_text),
-                            Style::default().fg(Color::Yellow)
-                        )));
-                    }
-                }
-            }
-            config::ModuleType::Break => {
-                let break_lines = app.config.get_break_lines();
-                for _ in 0..break_lines {
-                    lines.push(Line::from(""));
+                        format!("{}{}", selected_entry.command, args_str)
+                    };
+                    lines.push(Line::from(Span::styled(
+                        format!("Selected: {}", command_text),
+                        app.theme.selected
+                    )));
                 }
             }
-            config::ModuleType::Quit => {
+        }
+        config::ModuleType::Break => {
+            let break_lines = app.config.get_break_lines();
+            for _ in 0..break_lines {
+                lines.push(Line::from(""));
             }
-            config::ModuleType::SystemInfo => {
-                if let Some(ref _custom) = app.config.custom {
-                    let system_lines = render_system_info();
-                    lines.extend(system_lines);
-                }
+        }
+        config::ModuleType::Quit => {
+        }
+        config::ModuleType::SystemInfo => {
+            if let Some(ref _custom) = app.config.custom {
+                let system_lines = render_system_info(app.theme.system_info);
+                lines.extend(system_lines);
             }
+        }
 
-            config::ModuleType::Quote => {
-                if let Some(ref custom) = app.config.custom {
-                    let quote_lines = render_quote(&custom.quote);
-                    lines.extend(quote_lines);
-                }
+        config::ModuleType::Quote => {
+            if let Some(ref custom) = app.config.custom {
+                let quote_lines = render_quote(&custom.quote, app.theme.quote, app.theme.muted);
+                lines.extend(quote_lines);
             }
-            config::ModuleType::Uptime => {
-                if let Some(ref _custom) = app.config.custom {
-                    let uptime_lines = render_uptime();
-                    lines.extend(uptime_lines);
-                }
+        }
+        config::ModuleType::Uptime => {
+            if let Some(ref _custom) = app.config.custom {
+                let uptime_lines = render_uptime(app.theme.uptime);
+                lines.extend(uptime_lines);
             }
-            config::ModuleType::DiskUsage => {
-                if let Some(ref custom) = app.config.custom {
-                    let disk_lines = render_disk_usage(&custom.disk_usage);
-                    lines.extend(disk_lines);
-                }
+        }
+        config::ModuleType::DiskUsage => {
+            if let Some(ref custom) = app.config.custom {
+                let disk_lines = render_disk_usage(&custom.disk_usage, app.theme.disk, app.theme.muted);
+                lines.extend(disk_lines);
             }
-            config::ModuleType::Memory => {
-                if let Some(ref _custom) = app.config.custom {
-                    let memory_lines = render_memory();
-                    lines.extend(memory_lines);
-                }
+        }
+        config::ModuleType::Memory => {
+            if let Some(ref _custom) = app.config.custom {
+                let memory_lines = render_memory(app.theme.memory);
+                lines.extend(memory_lines);
             }
         }
     }
     
-    let paragraph = Paragraph::new(lines)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::White));
+    layout::Content {
+        lines,
+        targets: Vec::new(),
+    }
+}
+
+fn render_entries(group_name: &str, app: &App, entry_index: &mut usize) -> layout::Content {
+    let entries = app.config.get_entries(group_name);
+    let group = app.config.get_group(group_name);
+    let collapsed = app.filter.is_none() && app.collapsed.contains(group_name);
+    let first_index = *entry_index;
+    *entry_index += entries.len();
+    
+    let selected_style = app.theme.entry_selected;
+    
+    let title = group.and_then(|g| g.title.clone()).or_else(|| {
+        if collapsed {
+            Some(group_name.to_string())
+        } else {
+            None
+        }
+    });
+    let header = title.map(|title| {
+        let marker = if collapsed { "▸" } else { "▾" };
+        match group.and_then(|g| g.icon.as_ref()) {
+            Some(icon) => format!("{} {} {}", marker, icon, title),
+            None => format!("{} {}", marker, title),
+        }
+    });
+    
+    let mut rows: Vec<(usize, Line<'static>)> = Vec::new();
+    if !collapsed {
+        for (offset, entry) in entries.iter().enumerate() {
+            let selected = first_index + offset == app.selected;
+            let positions = match app.filter {
+                Some(ref filter) => match search::match_entry(filter, entry, &app.config.search) {
+                    Some(found) => found.positions,
+                    None => continue,
+                },
+                None => Vec::new(),
+            };
+            let hint = if app.config.hotkeys.show {
+                app.hotkey_label(first_index + offset)
+            } else {
+                None
+            };
+            let running = app.is_running(group_name, &entry.name);
+            rows.push((
+                first_index + offset,
+                render_entry_row(entry, selected, running, &app.theme, &positions, hint),
+            ));
+        }
+        if app.filter.is_some() && rows.is_empty() {
+            return layout::Content::default();
+        }
+    }
+    
+    let header_style = if collapsed && (first_index..*entry_index).contains(&app.selected) {
+        selected_style
+    } else {
+        app.theme.group_title
+    };
+    
+    let border = match group.map(|g| &g.border) {
+        Some(config::GroupBorder::Plain) => Some(border::PLAIN),
+        Some(config::GroupBorder::Rounded) => Some(border::ROUNDED),
+        Some(config::GroupBorder::Double) => Some(border::DOUBLE),
+        Some(config::GroupBorder::Thick) => Some(border::THICK),
+        Some(config::GroupBorder::None) | None => None,
+    };
+    
+    let mut content = layout::Content::default();
+    if header.is_some() {
+        content.targets.push((0, layout::Target::Group(first_index)));
+    }
+    
+    let Some(border) = border else {
+        if let Some(header) = header {
+            content.lines.push(Line::from(Span::styled(header, header_style)));
+        }
+        for (index, row) in rows {
+            content.targets.push((content.lines.len(), layout::Target::Entry(index)));
+            content.lines.push(row);
+        }
+        return content;
+    };
+    
+    let border_style = app.theme.border;
+    let header_width = header.as_ref().map(|h| Span::raw(h.as_str()).width() + 2).unwrap_or(0);
+    let inner_width = rows.iter().map(|(_, row)| row.width()).max().unwrap_or(0).max(header_width);
+    
+    let mut top = vec![Span::styled(border.top_left.to_string(), border_style)];
+    let mut top_used = 0;
+    if let Some(header) = header {
+        top.push(Span::styled(format!(" {} ", header), header_style));
+        top_used = header_width;
+    }
+    top.push(Span::styled(
+        border.horizontal_top.repeat(inner_width - top_used) + border.top_right,
+        border_style,
+    ));
+    content.lines.push(Line::from(top));
+    
+    for (index, row) in rows {
+        let padding = " ".repeat(inner_width - row.width());
+        let mut spans = vec![Span::styled(border.vertical_left.to_string(), border_style)];
+        spans.extend(row.spans);
+        spans.push(Span::raw(padding));
+        spans.push(Span::styled(border.vertical_right.to_string(), border_style));
+        content.targets.push((content.lines.len(), layout::Target::Entry(index)));
+        content.lines.push(Line::from(spans));
+    }
+    
+    content.lines.push(Line::from(Span::styled(
+        format!(
+            "{}{}{}",
+            border.bottom_left,
+            border.horizontal_bottom.repeat(inner_width),
+            border.bottom_right
+        ),
+        border_style,
+    )));
+    
+    content
+}
+
+fn render_entry_row(
+    entry: &config::MenuItem,
+    selected: bool,
+    running: bool,
+    theme: &theme::Theme,
+    positions: &[usize],
+    hint: Option<String>,
+) -> Line<'static> {
+    let style = if selected { theme.entry_selected } else { theme.entry };
+    let highlight = if selected {
+        style.add_modifier(Modifier::UNDERLINED)
+    } else {
+        style.patch(theme.highlight)
+    };
+    
+    let mut spans = vec![Span::styled("> ".to_string(), style)];
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (index, c) in entry.name.chars().enumerate() {
+        let highlighted = positions.contains(&index);
+        if highlighted != run_highlighted && !run.is_empty() {
+            let run_style = if run_highlighted { highlight } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    run.push_str("  ");
+    if run_highlighted {
+        let trailing = run.split_off(run.len() - 2);
+        spans.push(Span::styled(run, highlight));
+        spans.push(Span::styled(trailing, style));
+    } else {
+        spans.push(Span::styled(run, style));
+    }
+    
+    if running {
+        let running_style = if selected { style } else { theme.accent };
+        spans.push(Span::styled("● ".to_string(), running_style));
+    }
+    
+    if let Some(hint) = hint {
+        let hint_style = if selected { style } else { theme.muted };
+        spans.push(Span::styled(format!("{} ", hint), hint_style));
+    }
     
-    f.render_widget(paragraph, size);
+    Line::from(spans)
 }
 
 fn get_logo_text_with_type(logo_type: &config::LogoType, config: &Config) -> String {
@@ -456,8 +1339,7 @@ fn get_logo_text_with_type(logo_type: &config::LogoType, config: &Config) -> Str
         config::LogoType::Default => DOTT_LOGO.to_string(),
         config::LogoType::Custom => {
             if let Some(ref path) = config.custom_logo_path {
-                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                let expanded_path = path.replace("~", &home);
+                let expanded_path = expand::expand(path);
                 std::fs::read_to_string(&expanded_path).unwrap_or_else(|_| DOTT_LOGO.to_string())
             } else {
                 DOTT_LOGO.to_string()
@@ -513,7 +1395,7 @@ fn render_terminal_colors_lines(colors_config: &config::TerminalColorsConfig) ->
     lines
 }
 
-fn render_system_info() -> Vec<Line<'static>> {
+fn render_system_info(style: Style) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     
     let hostname = std::env::var("HOSTNAME")
@@ -537,13 +1419,13 @@ fn render_system_info() -> Vec<Line<'static>> {
     
     lines.push(Line::from(Span::styled(
         format!("󰇄 {} | {} | {}", hostname, os, kernel),
-        Style::default().fg(Color::Magenta)
+        style
     )));
     
     lines
 }
 
-fn render_quote(config: &config::QuoteConfig) -> Vec<Line<'static>> {
+fn render_quote(config: &config::QuoteConfig, style: Style, muted: Style) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     
     if !config.quotes.is_empty() {
@@ -558,7 +1440,7 @@ fn render_quote(config: &config::QuoteConfig) -> Vec<Line<'static>> {
                     && !current_line.is_empty() {
                     lines.push(Line::from(Span::styled(
                         current_line.clone(),
-                        Style::default().fg(Color::Yellow).add_modifier(Modifier::ITALIC)
+                        style
                     )));
                     current_line.clear();
                 }
@@ -571,21 +1453,21 @@ fn render_quote(config: &config::QuoteConfig) -> Vec<Line<'static>> {
             if !current_line.is_empty() {
                 lines.push(Line::from(Span::styled(
                     current_line,
-                    Style::default().fg(Color::Yellow).add_modifier(Modifier::ITALIC)
+                    style
                 )));
             }
         }
     } else {
         lines.push(Line::from(Span::styled(
             " Add quotes to your config!".to_string(),
-            Style::default().fg(Color::DarkGray)
+            muted
         )));
     }
     
     lines
 }
 
-fn render_uptime() -> Vec<Line<'static>> {
+fn render_uptime(style: Style) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     
     let uptime = if cfg!(target_os = "linux") {
@@ -614,19 +1496,20 @@ fn render_uptime() -> Vec<Line<'static>> {
     
     lines.push(Line::from(Span::styled(
         format!(" Uptime: {}", uptime),
-        Style::default().fg(Color::Green)
+        style
     )));
     
     lines
 }
 
-fn render_disk_usage(config: &config::DiskUsageConfig) -> Vec<Line<'static>> {
+fn render_disk_usage(config: &config::DiskUsageConfig, style: Style, muted: Style) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     
     let disks = Disks::new_with_refreshed_list();
+    let path = expand::expand(&config.path);
     
     for disk in &disks {
-        if disk.mount_point().to_str() == Some(&config.path) {
+        if disk.mount_point().to_str() == Some(path.as_str()) {
             let total = disk.total_space();
             let available = disk.available_space();
             let used = total - available;
@@ -641,7 +1524,7 @@ fn render_disk_usage(config: &config::DiskUsageConfig) -> Vec<Line<'static>> {
             
             lines.push(Line::from(Span::styled(
                 format!(" Disk: {} / {} GB ({}%)", used_gb, total_gb, usage_percent),
-                Style::default().fg(Color::Cyan)
+                style
             )));
             break;
         }
@@ -650,14 +1533,14 @@ fn render_disk_usage(config: &config::DiskUsageConfig) -> Vec<Line<'static>> {
     if lines.is_empty() {
         lines.push(Line::from(Span::styled(
             format!(" Disk: path not found ({})", config.path),
-            Style::default().fg(Color::DarkGray)
+            muted
         )));
     }
     
     lines
 }
 
-fn render_memory() -> Vec<Line<'static>> {
+fn render_memory(style: Style) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     
     let mut sys = System::new_all();
@@ -676,10 +1559,89 @@ fn render_memory() -> Vec<Line<'static>> {
     
     lines.push(Line::from(Span::styled(
         format!(" Memory: {} / {} MB ({}%)", used_gb, total_gb, usage_percent),
-        Style::default().fg(Color::LightBlue)
+        style
     )));
     
     lines
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<Config, config::ConfigError> {
+        Config::parse(content, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn broken_reload_keeps_last_good_config() {
+        let mut app = App::with_config(parse("[[entries]]\nname = \"Htop\"\ncommand = \"htop\"\n"));
+        app.reload_with(parse("[[entries]]\nname = \"Broken\"\nmode = \"sideways\"\n"));
+
+        assert!(app.config_error.is_some());
+        assert_eq!(app.config.entries[0].name, "Htop");
+        assert_eq!(app.all_entries.len(), 1);
+
+        app.reload_with(parse("[[entries]]\nname = \"Btop\"\ncommand = \"btop\"\n"));
+        assert!(app.config_error.is_none());
+        assert_eq!(app.config.entries[0].name, "Btop");
+    }
+
+    fn entries(names: &[&str]) -> String {
+        names
+            .iter()
+            .map(|name| format!("[[entries]]\nname = \"{}\"\ncommand = \"true\"\n\n", name))
+            .collect()
+    }
+
+    fn selected_name(app: &App) -> &str {
+        &app.all_entries[app.selected].1.name
+    }
+
+    #[test]
+    fn reload_keeps_the_selected_entry() {
+        let mut app = App::with_config(parse(&entries(&["Htop", "Btop", "Top"])));
+        app.selected = 1;
+
+        app.reload_with(parse(&entries(&["New", "Htop", "Btop", "Top"])));
+        assert_eq!(app.selected, 2);
+        assert_eq!(selected_name(&app), "Btop");
+    }
+
+    #[test]
+    fn reload_falls_back_when_the_selected_entry_is_gone() {
+        let mut app = App::with_config(parse(&entries(&["Htop", "Btop", "Top"])));
+        app.selected = 2;
+
+        app.reload_with(parse(&entries(&["Htop", "Btop", "Bottom"])));
+        assert_eq!(app.selected, 2);
+        assert_eq!(selected_name(&app), "Bottom");
+
+        app.reload_with(parse(&entries(&["Htop"])));
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn reload_keeps_groups_the_user_collapsed() {
+        let config = |collapsed: bool, extra: &str| {
+            format!(
+                "[[structure.build]]\nmodule = \"entries:git\"\n\n[groups.git]\ncollapsed = {}\n\n[[groups.git.entries]]\nname = \"Status\"\ncommand = \"git\"\n\n[[groups.git.entries]]\nname = \"{}\"\ncommand = \"git\"\n",
+                collapsed, extra
+            )
+        };
+        let mut app = App::with_config(parse(&config(false, "Log")));
+        assert!(!app.collapsed.contains("git"));
+        app.collapsed.insert("git".to_string());
+
+        app.reload_with(parse(&config(false, "Diff")));
+        assert!(app.collapsed.contains("git"));
+
+        app.reload_with(parse(&config(true, "Diff")));
+        assert!(app.collapsed.contains("git"));
+        app.collapsed.remove("git");
+        app.reload_with(parse(&config(true, "Log")));
+        assert!(!app.collapsed.contains("git"));
+    }
+}
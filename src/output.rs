@@ -0,0 +1,130 @@
+use crate::ansi::AnsiParser;
+use crate::exec::{Capture, Report};
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+const MAX_LINES: usize = 10_000;
+
+pub struct OutputPane {
+    title: String,
+    lines: Vec<Line<'static>>,
+    parser: AnsiParser,
+    capture: Option<Capture>,
+    report: Option<Report>,
+    scroll: usize,
+    follow: bool,
+    height: usize,
+}
+
+impl OutputPane {
+    pub fn new(title: &str, capture: Capture) -> OutputPane {
+        OutputPane {
+            title: title.to_string(),
+            lines: Vec::new(),
+            parser: AnsiParser::default(),
+            capture: Some(capture),
+            report: None,
+            scroll: 0,
+            follow: true,
+            height: 1,
+        }
+    }
+
+    pub fn poll(&mut self) {
+        let Some(ref mut capture) = self.capture else {
+            return;
+        };
+
+        let mut received = Vec::new();
+        let report = capture.poll(&mut received);
+        for text in received {
+            self.lines.push(self.parser.parse_line(&text));
+        }
+        if self.lines.len() > MAX_LINES {
+            let excess = self.lines.len() - MAX_LINES;
+            self.lines.drain(..excess);
+            self.scroll = self.scroll.saturating_sub(excess);
+        }
+
+        if report.is_some() {
+            self.report = report;
+            self.capture = None;
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self.scroll.saturating_add_signed(delta).min(self.max_scroll());
+        self.follow = self.scroll == self.max_scroll();
+    }
+
+    pub fn page(&mut self, forward: bool) {
+        let page = self.height.max(1) as isize;
+        self.scroll_by(if forward { page } else { -page });
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+        self.follow = self.max_scroll() == 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+        self.follow = true;
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, pane: &mut OutputPane, theme: &Theme) {
+    let area = area.inner(Margin {
+        horizontal: area.width / 20,
+        vertical: area.height / 10,
+    });
+
+    let status = match (&pane.report, &pane.capture) {
+        (Some(report), _) if report.success => Span::styled(format!(" ✓ {} ", report.message), theme.accent),
+        (Some(report), _) => Span::styled(format!(" ✗ {} ", report.message), theme.warning),
+        (None, Some(_)) => Span::styled(" running… ".to_string(), theme.muted),
+        (None, None) => Span::raw(""),
+    };
+
+    let block = Block::bordered()
+        .border_set(border::ROUNDED)
+        .border_style(theme.border)
+        .title(Line::from(Span::styled(format!(" {} ", pane.title), theme.group_title)))
+        .title_bottom(Line::from(status));
+
+    let inner = block.inner(area);
+    pane.height = inner.height as usize;
+    if pane.follow {
+        pane.scroll = pane.max_scroll();
+    }
+    pane.scroll = pane.scroll.min(pane.max_scroll());
+
+    let position = format!(
+        " {}-{}/{} ",
+        (pane.scroll + 1).min(pane.lines.len()),
+        (pane.scroll + pane.height).min(pane.lines.len()),
+        pane.lines.len()
+    );
+    let block = block.title_bottom(Line::from(Span::styled(position, theme.muted)).alignment(Alignment::Right));
+
+    let visible: Vec<Line<'static>> = pane
+        .lines
+        .iter()
+        .skip(pane.scroll)
+        .take(pane.height)
+        .cloned()
+        .collect();
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(visible).style(theme.entry).block(block), area);
+}
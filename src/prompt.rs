@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Prompt(String),
+    Choice(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub token: String,
+    pub kind: Kind,
+}
+
+pub fn placeholders(args: &[String]) -> Vec<Placeholder> {
+    let mut found: Vec<Placeholder> = Vec::new();
+    for arg in args {
+        for (_, placeholder) in tokens(arg) {
+            if !found.iter().any(|existing| existing.token == placeholder.token) {
+                found.push(placeholder);
+            }
+        }
+    }
+    found
+}
+
+pub fn substitute(args: &[String], answers: &HashMap<String, String>) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            let mut result = String::with_capacity(arg.len());
+            let mut last = 0;
+            for (range, placeholder) in tokens(arg) {
+                if let Some(answer) = answers.get(&placeholder.token) {
+                    result.push_str(&arg[last..range.start]);
+                    result.push_str(answer);
+                    last = range.end;
+                }
+            }
+            result.push_str(&arg[last..]);
+            result
+        })
+        .collect()
+}
+
+fn tokens(arg: &str) -> Vec<(Range<usize>, Placeholder)> {
+    let mut tokens = Vec::new();
+    let mut cursor = 0;
+
+    while let Some(offset) = arg[cursor..].find('{') {
+        let start = cursor + offset;
+        let Some(length) = arg[start..].find('}') else {
+            break;
+        };
+        let end = start + length + 1;
+        match parse(&arg[start + 1..end - 1]) {
+            Some(kind) => {
+                tokens.push((
+                    start..end,
+                    Placeholder {
+                        token: arg[start..end].to_string(),
+                        kind,
+                    },
+                ));
+                cursor = end;
+            }
+            None => cursor = start + 1,
+        }
+    }
+
+    tokens
+}
+
+fn parse(inner: &str) -> Option<Kind> {
+    let (name, value) = inner.split_once(':').unwrap_or((inner, ""));
+    match name.trim() {
+        "prompt" => {
+            let label = value.trim();
+            Some(Kind::Prompt(if label.is_empty() { "Value".to_string() } else { label.to_string() }))
+        }
+        "choice" => {
+            let options: Vec<String> = value
+                .split(',')
+                .map(|option| option.trim().to_string())
+                .filter(|option| !option.is_empty())
+                .collect();
+            if options.is_empty() {
+                None
+            } else {
+                Some(Kind::Choice(options))
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_prompts_and_choices_once() {
+        let found = placeholders(&args(&["{prompt:Branch}", "--{choice: a, b ,}", "{prompt:Branch}", "{prompt}"]));
+        assert_eq!(
+            found,
+            [
+                Placeholder {
+                    token: "{prompt:Branch}".to_string(),
+                    kind: Kind::Prompt("Branch".to_string()),
+                },
+                Placeholder {
+                    token: "{choice: a, b ,}".to_string(),
+                    kind: Kind::Choice(vec!["a".to_string(), "b".to_string()]),
+                },
+                Placeholder {
+                    token: "{prompt}".to_string(),
+                    kind: Kind::Prompt("Value".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_other_braces() {
+        assert!(placeholders(&args(&["{choice:}", "{choice: ,}", "${HOME}", "{name}", "{prompt:x"])).is_empty());
+    }
+
+    #[test]
+    fn substitutes_answers() {
+        let answers = HashMap::from([
+            ("{prompt:Name}".to_string(), "world".to_string()),
+            ("{choice:a,b}".to_string(), "b".to_string()),
+        ]);
+        assert_eq!(
+            substitute(&args(&["hello {prompt:Name}!", "{choice:a,b}{choice:a,b}", "{prompt:Other}", "{x}"]), &answers),
+            ["hello world!", "bb", "{prompt:Other}", "{x}"]
+        );
+    }
+
+    #[test]
+    fn answers_are_inserted_literally() {
+        let answers = HashMap::from([("{prompt}".to_string(), "{prompt} $HOME".to_string())]);
+        assert_eq!(substitute(&args(&["{prompt}"]), &answers), ["{prompt} $HOME"]);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::config::{MenuItem, SearchConfig};
+
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = FuzzyMatch::default();
+    let mut cursor = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in pattern.chars().filter(|c| !c.is_whitespace()) {
+        while cursor < chars.len() && !chars_match(wanted, chars[cursor]) {
+            cursor += 1;
+        }
+        if cursor == chars.len() {
+            return None;
+        }
+
+        result.score += 1;
+        if cursor == 0 || !chars[cursor - 1].is_alphanumeric() {
+            result.score += 3;
+        }
+        match previous {
+            Some(p) if p + 1 == cursor => result.score += 5,
+            Some(p) => result.score -= ((cursor - p - 1) as i64).min(3),
+            None => result.score -= (cursor as i64).min(3),
+        }
+
+        result.positions.push(cursor);
+        previous = Some(cursor);
+        cursor += 1;
+    }
+
+    Some(result)
+}
+
+pub fn match_entry(pattern: &str, entry: &MenuItem, search: &SearchConfig) -> Option<FuzzyMatch> {
+    let mut candidates = Vec::new();
+    if search.commands && !entry.command.is_empty() {
+        let mut command = entry.command.clone();
+        for arg in &entry.args {
+            command.push(' ');
+            command.push_str(arg);
+        }
+        candidates.push(command);
+    }
+    if search.tags {
+        candidates.extend(entry.tags.iter().cloned());
+    }
+
+    let other_score = candidates
+        .iter()
+        .filter_map(|text| fuzzy_match(pattern, text))
+        .map(|found| found.score - 1)
+        .max();
+
+    match (fuzzy_match(pattern, &entry.name), other_score) {
+        (Some(mut found), Some(score)) => {
+            found.score = found.score.max(score);
+            Some(found)
+        }
+        (Some(found), None) => Some(found),
+        (None, Some(score)) => Some(FuzzyMatch {
+            score,
+            positions: Vec::new(),
+        }),
+        (None, None) => None,
+    }
+}
+
+fn chars_match(wanted: char, candidate: char) -> bool {
+    wanted == candidate || wanted.to_lowercase().eq(candidate.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_in_order_ignoring_case() {
+        let found = fuzzy_match("gs", "Git Status").expect("should match");
+        assert_eq!(found.positions, [0, 4]);
+        assert_eq!(found.score, 5);
+        assert!(fuzzy_match("sg", "Git Status").is_none());
+        assert!(fuzzy_match("gx", "Git Status").is_none());
+    }
+
+    #[test]
+    fn consecutive_and_word_start_matches_score_higher() {
+        let consecutive = fuzzy_match("git", "Git Status").unwrap();
+        let scattered = fuzzy_match("git", "Go Into Tree").unwrap();
+        assert_eq!(consecutive.positions, [0, 1, 2]);
+        assert_eq!(scattered.positions, [0, 3, 5]);
+        assert!(consecutive.score > scattered.score);
+
+        let word_start = fuzzy_match("b", "foo bar").unwrap();
+        let mid_word = fuzzy_match("b", "foobar").unwrap();
+        assert!(word_start.score > mid_word.score);
+    }
+
+    #[test]
+    fn whitespace_in_the_pattern_is_ignored() {
+        assert_eq!(fuzzy_match("g s", "Git Status").unwrap().positions, [0, 4]);
+        assert_eq!(fuzzy_match("", "anything").unwrap().positions, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn entries_match_tags_and_commands_without_positions() {
+        let entry = MenuItem {
+            name: "Monitor".to_string(),
+            command: "btop".to_string(),
+            tags: vec!["system".to_string()],
+            ..Default::default()
+        };
+        let search = SearchConfig::default();
+        assert!(match_entry("sys", &entry, &search).unwrap().positions.is_empty());
+        assert!(match_entry("btop", &entry, &search).is_none());
+
+        let search = SearchConfig {
+            commands: true,
+            tags: false,
+        };
+        assert!(match_entry("btop", &entry, &search).is_some());
+        assert!(match_entry("sys", &entry, &search).is_none());
+    }
+}
@@ -0,0 +1,396 @@
+use crate::config::{self, ColorSpec, StyleSpec, StyleTable, ThemeConfig};
+use crate::validate;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const BUNDLED_THEMES: &[(&str, &str)] = &[
+    ("default", include_str!("../themes/default.toml")),
+    ("nord", include_str!("../themes/nord.toml")),
+    ("gruvbox", include_str!("../themes/gruvbox.toml")),
+    ("dracula", include_str!("../themes/dracula.toml")),
+    ("mono", include_str!("../themes/mono.toml")),
+];
+
+const MAX_THEME_DEPTH: usize = 8;
+
+const ROLES: &[&str] = &[
+    "logo",
+    "entry",
+    "entry_selected",
+    "highlight",
+    "group_title",
+    "border",
+    "muted",
+    "accent",
+    "warning",
+    "clock",
+    "selected",
+    "quote",
+    "system_info",
+    "uptime",
+    "disk",
+    "memory",
+];
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub logo: Style,
+    pub entry: Style,
+    pub entry_selected: Style,
+    pub highlight: Style,
+    pub group_title: Style,
+    pub border: Style,
+    pub muted: Style,
+    pub accent: Style,
+    pub warning: Style,
+    pub clock: Style,
+    pub selected: Style,
+    pub quote: Style,
+    pub system_info: Style,
+    pub uptime: Style,
+    pub disk: Style,
+    pub memory: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            logo: Style::default().fg(Color::Cyan),
+            entry: Style::default().fg(Color::White),
+            entry_selected: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+            highlight: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            group_title: Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            border: Style::default().fg(Color::DarkGray),
+            muted: Style::default().fg(Color::DarkGray),
+            accent: Style::default().fg(Color::Cyan),
+            warning: Style::default().fg(Color::Yellow),
+            clock: Style::default().fg(Color::Cyan),
+            selected: Style::default().fg(Color::Yellow),
+            quote: Style::default().fg(Color::Yellow).add_modifier(Modifier::ITALIC),
+            system_info: Style::default().fg(Color::Magenta),
+            uptime: Style::default().fg(Color::Green),
+            disk: Style::default().fg(Color::Cyan),
+            memory: Style::default().fg(Color::LightBlue),
+        }
+    }
+}
+
+impl Theme {
+    pub fn from_config(config: &ThemeConfig, themes_dir: &Path) -> (Theme, Vec<String>) {
+        let mut warnings = Vec::new();
+        let theme = resolve(config, themes_dir, 0, &mut warnings);
+        (theme, warnings)
+    }
+
+    pub fn named(name: &str, themes_dir: &Path) -> (Theme, Vec<String>) {
+        let mut warnings = Vec::new();
+        let theme = load_named(name, themes_dir, 0, &mut warnings);
+        (theme, warnings)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Style> {
+        let style = match role {
+            "logo" => &mut self.logo,
+            "entry" => &mut self.entry,
+            "entry_selected" => &mut self.entry_selected,
+            "highlight" => &mut self.highlight,
+            "group_title" => &mut self.group_title,
+            "border" => &mut self.border,
+            "muted" => &mut self.muted,
+            "accent" => &mut self.accent,
+            "warning" => &mut self.warning,
+            "clock" => &mut self.clock,
+            "selected" => &mut self.selected,
+            "quote" => &mut self.quote,
+            "system_info" => &mut self.system_info,
+            "uptime" => &mut self.uptime,
+            "disk" => &mut self.disk,
+            "memory" => &mut self.memory,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+pub fn available_themes(themes_dir: &Path) -> Vec<(String, bool)> {
+    let mut themes: Vec<(String, bool)> = BUNDLED_THEMES
+        .iter()
+        .map(|(name, _)| (name.to_string(), false))
+        .collect();
+
+    if let Ok(dir) = fs::read_dir(themes_dir) {
+        let mut user_themes: Vec<String> = dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .collect();
+        user_themes.sort();
+        for name in user_themes {
+            match themes.iter_mut().find(|(existing, _)| *existing == name) {
+                Some(existing) => existing.1 = true,
+                None => themes.push((name, true)),
+            }
+        }
+    }
+
+    themes
+}
+
+pub fn theme_path(name: &str, themes_dir: &Path) -> PathBuf {
+    themes_dir.join(format!("{}.toml", name))
+}
+
+fn resolve(config: &ThemeConfig, themes_dir: &Path, depth: usize, warnings: &mut Vec<String>) -> Theme {
+    let mut theme = match config.name {
+        Some(ref name) => load_named(name, themes_dir, depth + 1, warnings),
+        None => Theme::default(),
+    };
+    apply_styles(&mut theme, config, warnings);
+    theme
+}
+
+fn apply_styles(theme: &mut Theme, config: &ThemeConfig, warnings: &mut Vec<String>) {
+    for (role, spec) in &config.styles {
+        let Some(style) = theme.role_mut(role) else {
+            match validate::suggest(role, ROLES.iter().copied()) {
+                Some(suggestion) => {
+                    warnings.push(format!("Unknown theme role \"{}\", did you mean \"{}\"?", role, suggestion))
+                }
+                None => warnings.push(format!("Unknown theme role \"{}\"", role)),
+            }
+            continue;
+        };
+        if let Err(e) = apply_spec(style, spec) {
+            warnings.push(format!("Theme role \"{}\": {}", role, e));
+        }
+    }
+}
+
+fn load_named(name: &str, themes_dir: &Path, depth: usize, warnings: &mut Vec<String>) -> Theme {
+    load_theme(name, themes_dir, depth, true, warnings)
+}
+
+fn load_theme(name: &str, themes_dir: &Path, depth: usize, allow_user: bool, warnings: &mut Vec<String>) -> Theme {
+    if depth > MAX_THEME_DEPTH {
+        warnings.push(format!("Theme \"{}\" extends too many themes", name));
+        return Theme::default();
+    }
+
+    let path = theme_path(name, themes_dir);
+    let bundled = BUNDLED_THEMES.iter().find(|(bundled, _)| *bundled == name);
+    let (source, content, from_user) = if allow_user && path.exists() {
+        match fs::read_to_string(&path) {
+            Ok(content) => (path.display().to_string(), content, true),
+            Err(e) => {
+                warnings.push(format!("Error reading theme {}: {}", path.display(), e));
+                return Theme::default();
+            }
+        }
+    } else if let Some((_, content)) = bundled {
+        (format!("bundled theme \"{}\"", name), content.to_string(), false)
+    } else {
+        let names: Vec<String> = available_themes(themes_dir).into_iter().map(|(name, _)| name).collect();
+        warnings.push(format!(
+            "Unknown theme \"{}\", available themes: {}",
+            name,
+            names.join(", ")
+        ));
+        return Theme::default();
+    };
+
+    let config = match toml::from_str::<ThemeConfig>(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            warnings.push(format!("Error parsing {}: {}", source, e));
+            return Theme::default();
+        }
+    };
+
+    if config.name.as_deref() != Some(name) {
+        return resolve(&config, themes_dir, depth, warnings);
+    }
+
+    // A user theme named after a bundled one may build on top of it.
+    let mut theme = if from_user && bundled.is_some() {
+        load_theme(name, themes_dir, depth + 1, false, warnings)
+    } else {
+        warnings.push(format!("Theme \"{}\" cannot extend itself", name));
+        Theme::default()
+    };
+    apply_styles(&mut theme, &config, warnings);
+    theme
+}
+
+fn apply_spec(style: &mut Style, spec: &StyleSpec) -> Result<(), String> {
+    match spec {
+        StyleSpec::Color(color) => {
+            *style = style.fg(parse_color(color)?);
+        }
+        StyleSpec::Style(StyleTable { fg, bg, modifiers }) => {
+            let mut updated = Style::default();
+            if let Some(fg) = fg {
+                updated = updated.fg(parse_color(fg)?);
+            }
+            if let Some(bg) = bg {
+                updated = updated.bg(parse_color(bg)?);
+            }
+            for name in modifiers {
+                updated = updated.add_modifier(parse_modifier(name)?);
+            }
+            *style = updated;
+        }
+        StyleSpec::Invalid(toml::Value::Table(table)) => {
+            // Parse the table again on its own to find out which field is wrong.
+            let error = StyleTable::deserialize(toml::Value::Table(table.clone())).err();
+            return Err(error.map_or_else(
+                || "invalid style table".to_string(),
+                |e| config::readable_message(e.message()),
+            ));
+        }
+        StyleSpec::Invalid(value) => {
+            return Err(format!(
+                "expected a color or a table with fg, bg and modifiers, found {}",
+                value.type_str()
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_color(spec: &ColorSpec) -> Result<Color, String> {
+    let name = match spec {
+        ColorSpec::Index(index) => {
+            return u8::try_from(*index)
+                .map(Color::Indexed)
+                .map_err(|_| format!("color index {} is out of range (0-255)", index))
+        }
+        ColorSpec::Name(name) => name.trim().to_lowercase(),
+    };
+
+    if let Some(hex) = name.strip_prefix('#') {
+        let channel = |range: std::ops::Range<usize>| {
+            hex.get(range)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| format!("invalid hex color \"{}\"", name))
+        };
+        if hex.len() != 6 {
+            return Err(format!("invalid hex color \"{}\"", name));
+        }
+        return Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?));
+    }
+
+    if let Ok(index) = name.parse::<u8>() {
+        return Ok(Color::Indexed(index));
+    }
+
+    let color = match name.replace(['_', '-', ' '], "").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return Err(format!("unknown color \"{}\"", name)),
+    };
+    Ok(color)
+}
+
+fn parse_modifier(name: &str) -> Result<Modifier, String> {
+    let modifier = match name.trim().to_lowercase().as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underline" | "underlined" => Modifier::UNDERLINED,
+        "reverse" | "reversed" => Modifier::REVERSED,
+        "strikethrough" | "crossed_out" => Modifier::CROSSED_OUT,
+        "blink" | "slow_blink" => Modifier::SLOW_BLINK,
+        "hidden" => Modifier::HIDDEN,
+        other => return Err(format!("unknown modifier \"{}\"", other)),
+    };
+    Ok(modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn unknown_role_suggests_a_close_name() {
+        let config = ThemeConfig {
+            name: None,
+            styles: BTreeMap::from([("entry_selcted".to_string(), StyleSpec::Color(ColorSpec::Index(1)))]),
+        };
+        let (_, warnings) = Theme::from_config(&config, Path::new("/nonexistent"));
+        assert_eq!(warnings, ["Unknown theme role \"entry_selcted\", did you mean \"entry_selected\"?"]);
+    }
+
+    #[test]
+    fn parses_named_indexed_and_hex_colors() {
+        let name = |name: &str| parse_color(&ColorSpec::Name(name.to_string()));
+        assert_eq!(name("red"), Ok(Color::Red));
+        assert_eq!(name(" Light-Blue "), Ok(Color::LightBlue));
+        assert_eq!(name("dark_grey"), Ok(Color::DarkGray));
+        assert_eq!(name("208"), Ok(Color::Indexed(208)));
+        assert_eq!(name("#FF8800"), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_color(&ColorSpec::Index(0)), Ok(Color::Indexed(0)));
+        assert_eq!(parse_color(&ColorSpec::Index(255)), Ok(Color::Indexed(255)));
+    }
+
+    #[test]
+    fn rejects_bad_colors() {
+        let name = |name: &str| parse_color(&ColorSpec::Name(name.to_string()));
+        assert_eq!(parse_color(&ColorSpec::Index(300)), Err("color index 300 is out of range (0-255)".to_string()));
+        assert_eq!(parse_color(&ColorSpec::Index(-1)), Err("color index -1 is out of range (0-255)".to_string()));
+        assert_eq!(name("#12345"), Err("invalid hex color \"#12345\"".to_string()));
+        assert_eq!(name("#gg0000"), Err("invalid hex color \"#gg0000\"".to_string()));
+        assert_eq!(name("chartreuse"), Err("unknown color \"chartreuse\"".to_string()));
+    }
+
+    #[test]
+    fn bad_role_values_are_warnings() {
+        let content = "[theme]\nlogo = 300\nentry = true\nborder = { fg = [1] }\naccent = \"magenta\"\n";
+        let config = Config::parse(content, Path::new("config.toml")).expect("bad roles should not stop loading");
+        let (theme, warnings) = Theme::from_config(&config.theme, Path::new("/nonexistent"));
+        assert_eq!(
+            warnings,
+            [
+                "Theme role \"border\": expected a color name, a \"#rrggbb\" value or a number from 0 to 255",
+                "Theme role \"entry\": expected a color or a table with fg, bg and modifiers, found boolean",
+                "Theme role \"logo\": color index 300 is out of range (0-255)",
+            ]
+        );
+        assert_eq!(theme.logo, Theme::default().logo);
+        assert_eq!(theme.accent.fg, Some(Color::Magenta));
+    }
+
+    #[test]
+    fn theme_role_typos_are_reported() {
+        let config = Config::parse("[theme]\nentry_selected = { fg = \"red\", bgg = \"blue\" }\n", Path::new("config.toml")).unwrap();
+        let (_, warnings) = Theme::from_config(&config.theme, Path::new("/nonexistent"));
+        assert_eq!(
+            warnings,
+            ["Theme role \"entry_selected\": unknown field `bgg`, expected one of `fg`, `bg`, `modifiers`"]
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use crate::config::{
+    BreakConfig, Config, CustomModules, DiskUsageConfig, EntryGroup, HotkeysConfig, KeySegment, LaunchConfig,
+    MenuItem, ModuleType, MouseConfig, QuoteConfig, SearchConfig, Structure, StructureBuildItem,
+    LogoType, TerminalColorsConfig, MODULE_NAMES,
+};
+use crate::exec::find_in_path;
+use crate::expand::expand;
+use crate::keys::{self, Bindings};
+use crate::theme::Theme;
+use serde::de::{self, Deserialize, Visitor};
+use std::fmt::Write;
+use std::path::Path;
+
+const SHELL_BUILTINS: &[&str] = &[".", "alias", "cd", "eval", "exec", "export", "set", "source", "unset"];
+
+pub fn collect(config: &Config) -> Vec<String> {
+    let mut warnings = validate(config);
+    let (bindings, binding_warnings) = Bindings::from_config(&config.keys);
+    warnings.extend(binding_warnings);
+    let (_, hotkey_warnings) = keys::assign_hotkeys(&config.get_placed_entries(), &config.hotkeys, &bindings);
+    warnings.extend(hotkey_warnings);
+    let (_, theme_warnings) = Theme::from_config(&config.theme, &Config::themes_dir());
+    warnings.extend(theme_warnings);
+    warnings
+}
+
+pub fn validate(config: &Config) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for path in &config.unknown_keys {
+        let Some(KeySegment::Key(key)) = path.last() else {
+            continue;
+        };
+        let mut message = format!("Unknown key \"{}\"", format_path(path));
+        let known = known_keys(&path[..path.len() - 1]);
+        if let Some(suggestion) = suggest(key, known.iter().copied()) {
+            let _ = write!(message, ", did you mean \"{}\"?", suggestion);
+        }
+        warnings.push(message);
+    }
+
+    check_build_items(&config.structure.build, "structure.build", &mut warnings);
+
+    let mut modules = Vec::new();
+    collect_module_names(&config.structure.build, &mut modules);
+    let groups = group_names(config);
+    for name in modules {
+        match config.parse_module(name) {
+            None => {
+                let mut message = format!("Unknown module \"{}\"", name);
+                if let Some(suggestion) = suggest(name, MODULE_NAMES.iter().copied()) {
+                    let _ = write!(message, ", did you mean \"{}\"?", suggestion);
+                }
+                warnings.push(message);
+            }
+            Some(ModuleType::Logo(_)) if !MODULE_NAMES.contains(&name) => {
+                let mut message = format!("Unknown logo module \"{}\"", name);
+                if let Some(suggestion) = suggest(name, MODULE_NAMES.iter().copied()) {
+                    let _ = write!(message, ", did you mean \"{}\"?", suggestion);
+                }
+                warnings.push(message);
+            }
+            Some(ModuleType::Entries(group)) if config.get_entries(&group).is_empty() => {
+                let mut message = format!("Module \"{}\" refers to a group without entries", name);
+                if let Some(suggestion) = suggest(&group, groups.iter().map(String::as_str)) {
+                    let _ = write!(message, ", did you mean \"entries:{}\"?", suggestion);
+                }
+                warnings.push(message);
+            }
+            Some(module) if config.custom.is_none() && needs_custom(&module) => {
+                warnings.push(format!(
+                    "Module \"{}\" needs a [custom] section and will stay empty",
+                    name
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    warnings
+}
+
+pub fn missing_files(config: &Config) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let mut logos: Vec<LogoType> = Vec::new();
+    let mut placed_groups: Vec<String> = Vec::new();
+    for module in config.get_ordered_modules() {
+        match module.module_type {
+            ModuleType::Logo(logo_type) if !logos.contains(&logo_type) => logos.push(logo_type),
+            ModuleType::Entries(group) if !placed_groups.contains(&group) => placed_groups.push(group),
+            _ => {}
+        }
+    }
+    for logo_type in logos {
+        let (kind, key, path) = match logo_type {
+            LogoType::Default => continue,
+            LogoType::Custom => ("custom", "custom_logo_path", &config.custom_logo_path),
+            LogoType::Image => ("image", "image_logo_path", &config.image_logo_path),
+        };
+        match path {
+            None => problems.push(format!("A {} logo is shown but {} is not set", kind, key)),
+            Some(path) if !Path::new(&expand(path)).is_file() => {
+                problems.push(format!("Logo file \"{}\" does not exist", path));
+            }
+            Some(_) => {}
+        }
+    }
+
+    if let Some(ref editor) = config.editor {
+        if let Some(program) = editor.split_whitespace().next() {
+            if !program_exists(&expand(program)) {
+                problems.push(format!("Editor \"{}\" was not found on $PATH", program));
+            }
+        }
+    }
+
+    for group in placed_groups {
+        for entry in config.get_entries(&group) {
+            if entry.builtin().is_some() {
+                continue;
+            }
+            let program = if entry.shell {
+                match entry.command.split_whitespace().next() {
+                    Some(word) if is_plain_word(word) && !SHELL_BUILTINS.contains(&word) => expand(word),
+                    _ => String::new(),
+                }
+            } else if entry.expand {
+                expand(&entry.command)
+            } else {
+                entry.command.clone()
+            };
+
+            if entry.command.trim().is_empty() {
+                problems.push(format!("Entry \"{}\" has no command", entry.name));
+            } else if !program.is_empty() && !program_exists(&program) {
+                problems.push(format!("Entry \"{}\": command \"{}\" was not found on $PATH", entry.name, program));
+            }
+
+            if let Some(ref cwd) = entry.cwd {
+                let dir = if entry.expand { expand(cwd) } else { cwd.clone() };
+                if !Path::new(&dir).is_dir() {
+                    problems.push(format!("Entry \"{}\": working directory \"{}\" does not exist", entry.name, dir));
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+fn program_exists(program: &str) -> bool {
+    if program.contains('/') {
+        Path::new(program).is_file()
+    } else {
+        find_in_path(program)
+    }
+}
+
+fn is_plain_word(word: &str) -> bool {
+    word.chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | '~' | '+'))
+}
+
+fn needs_custom(module: &ModuleType) -> bool {
+    matches!(
+        module,
+        ModuleType::Colors
+            | ModuleType::Clock
+            | ModuleType::Selected
+            | ModuleType::SystemInfo
+            | ModuleType::Quote
+            | ModuleType::Uptime
+            | ModuleType::DiskUsage
+            | ModuleType::Memory
+    )
+}
+
+fn check_build_items(items: &[StructureBuildItem], path: &str, warnings: &mut Vec<String>) {
+    for (index, item) in items.iter().enumerate() {
+        let path = format!("{}[{}]", path, index);
+        let containers = [("row", &item.row), ("column", &item.column), ("grid", &item.grid)];
+
+        // Same order as Config::layout_child, which only uses the first one set.
+        let mut used: Vec<&str> = item.module.iter().map(|_| "module").collect();
+        used.extend(containers.iter().filter(|(_, items)| !items.is_empty()).map(|(name, _)| *name));
+        match used.as_slice() {
+            [] => warnings.push(format!(
+                "Build item \"{}\" has no module and no row, column or grid with items and is skipped",
+                path
+            )),
+            [_] => {}
+            [first, ..] => warnings.push(format!(
+                "Build item \"{}\" sets more than one of module, row, column and grid, only \"{}\" is used",
+                path, first
+            )),
+        }
+
+        for (name, children) in containers {
+            check_build_items(children, &format!("{}.{}", path, name), warnings);
+        }
+    }
+}
+
+fn collect_module_names<'a>(items: &'a [StructureBuildItem], names: &mut Vec<&'a str>) {
+    for item in items {
+        if let Some(ref module) = item.module {
+            names.push(module);
+        }
+        collect_module_names(&item.row, names);
+        collect_module_names(&item.column, names);
+        collect_module_names(&item.grid, names);
+    }
+}
+
+fn group_names(config: &Config) -> Vec<String> {
+    let legacy = [
+        ("entries", &config.entries),
+        ("entries2", &config.entries2),
+        ("entries3", &config.entries3),
+        ("entries4", &config.entries4),
+        ("entries5", &config.entries5),
+    ];
+    legacy
+        .iter()
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(name, _)| name.to_string())
+        .chain(config.groups.keys().cloned())
+        .collect()
+}
+
+fn format_path(path: &[KeySegment]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            KeySegment::Key(key) if formatted.is_empty() => formatted.push_str(key),
+            KeySegment::Key(key) => {
+                let _ = write!(formatted, ".{}", key);
+            }
+            KeySegment::Index(index) => {
+                let _ = write!(formatted, "[{}]", index);
+            }
+        }
+    }
+    formatted
+}
+
+fn known_keys(parent: &[KeySegment]) -> &'static [&'static str] {
+    let keys: Vec<&str> = parent
+        .iter()
+        .filter_map(|segment| match segment {
+            KeySegment::Key(key) => Some(key.as_str()),
+            KeySegment::Index(_) => None,
+        })
+        .collect();
+
+    match keys.as_slice() {
+        [] => field_names::<Config>(),
+        ["structure"] => field_names::<Structure>(),
+        ["structure", "build", ..] => field_names::<StructureBuildItem>(),
+        ["entries" | "entries2" | "entries3" | "entries4" | "entries5"] => field_names::<MenuItem>(),
+        ["groups", _] => field_names::<EntryGroup>(),
+        ["groups", _, "entries"] => field_names::<MenuItem>(),
+        ["search"] => field_names::<SearchConfig>(),
+        ["hotkeys"] => field_names::<HotkeysConfig>(),
+        ["mouse"] => field_names::<MouseConfig>(),
+        ["launch"] => field_names::<LaunchConfig>(),
+        ["custom"] => field_names::<CustomModules>(),
+        ["custom", "terminal_colors"] => field_names::<TerminalColorsConfig>(),
+        ["custom", "break"] => field_names::<BreakConfig>(),
+        ["custom", "quote"] => field_names::<QuoteConfig>(),
+        ["custom", "disk_usage"] => field_names::<DiskUsageConfig>(),
+        _ => &[],
+    }
+}
+
+fn field_names<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    struct Introspect<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> de::Deserializer<'de> for Introspect<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("not a struct"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(Introspect(&mut fields));
+    fields
+}
+
+pub fn suggest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (name.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance > 0 && *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Config {
+        Config::parse(content, Path::new("test.toml")).expect("config should parse")
+    }
+
+    #[test]
+    fn reports_unknown_module() {
+        let config = parse("[[structure.build]]\nmodule = \"clok\"\n");
+        assert_eq!(validate(&config), ["Unknown module \"clok\", did you mean \"clock\"?"]);
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        let config = parse("logo_typ = \"default\"\n\n[[entries]]\nname = \"Htop\"\ncommand = \"htop\"\nmdoe = \"detach\"\n\n[search]\nenabled = true\n");
+        assert_eq!(
+            validate(&config),
+            [
+                "Unknown key \"logo_typ\", did you mean \"logo_type\"?",
+                "Unknown key \"entries[0].mdoe\", did you mean \"mode\"?",
+                "Unknown key \"search.enabled\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_ambiguous_and_empty_build_items() {
+        let config = parse(
+            "[[structure.build]]\nmodule = \"help\"\nrow = [{ module = \"quit\" }]\n\n[[structure.build]]\nrow = []\n\n[[structure.build]]\nsize = 3\n\n[[structure.build]]\ncolumn = [{ module = \"help\" }, { align = \"left\" }]\n",
+        );
+        assert_eq!(
+            validate(&config),
+            [
+                "Build item \"structure.build[0]\" sets more than one of module, row, column and grid, only \"module\" is used",
+                "Build item \"structure.build[1]\" has no module and no row, column or grid with items and is skipped",
+                "Build item \"structure.build[2]\" has no module and no row, column or grid with items and is skipped",
+                "Build item \"structure.build[3].column[1]\" has no module and no row, column or grid with items and is skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_empty_groups_and_missing_custom_section() {
+        let config = parse(
+            "[[structure.build]]\nmodule = \"entries:tols\"\n\n[[structure.build]]\nmodule = \"clock\"\n\n[[groups.tools.entries]]\nname = \"Htop\"\ncommand = \"htop\"\n",
+        );
+        assert_eq!(
+            validate(&config),
+            [
+                "Module \"entries:tols\" refers to a group without entries, did you mean \"entries:tools\"?",
+                "Module \"clock\" needs a [custom] section and will stay empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_logo_module() {
+        let config = parse("[[structure.build]]\nmodule = \"logo:imgae\"\n");
+        assert_eq!(validate(&config), ["Unknown logo module \"logo:imgae\", did you mean \"logo:image\"?"]);
+    }
+
+    #[test]
+    fn collect_includes_key_hotkey_and_theme_warnings() {
+        let config = parse(
+            "[keys]\nup = [\"j\"]\n\n[theme]\nlogo = \"chartreuse\"\n\n[[entries]]\nname = \"Htop\"\ncommand = \"htop\"\nkey = \"q\"\n\n[[structure.build]]\nmodule = \"entries\"\n",
+        );
+        assert_eq!(
+            collect(&config),
+            [
+                "Key \"j\" for \"down\" is already bound to \"up\"",
+                "Entry \"Htop\": key \"q\" is already used by dott and was ignored",
+                "Theme role \"logo\": unknown color \"chartreuse\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn suggest_picks_the_closest_name() {
+        let names = ["clock", "colors", "quote", "quit"];
+        assert_eq!(suggest("clok", names.iter().copied()), Some("clock"));
+        assert_eq!(suggest("qoute", names.iter().copied()), Some("quote"));
+        assert_eq!(suggest("Colors", names.iter().copied()), None);
+        assert_eq!(suggest("weather", names.iter().copied()), None);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("clock", "clock"), 0);
+        assert_eq!(edit_distance("clok", "clock"), 1);
+        assert_eq!(edit_distance("mdoe", "mode"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("Quit", "quit"), 0);
+    }
+
+    #[test]
+    fn reports_missing_logo_file() {
+        let config = parse("logo_type = \"custom\"\ncustom_logo_path = \"/nonexistent/dott/logo.txt\"\n");
+        assert!(missing_files(&config).contains(&"Logo file \"/nonexistent/dott/logo.txt\" does not exist".to_string()));
+    }
+
+    #[test]
+    fn reports_missing_command() {
+        let config = parse(
+            "[[structure.build]]\nmodule = \"entries\"\n\n[[entries]]\nname = \"Nope\"\ncommand = \"dott-missing-command\"\n",
+        );
+        assert_eq!(
+            missing_files(&config),
+            ["Entry \"Nope\": command \"dott-missing-command\" was not found on $PATH"]
+        );
+    }
+
+    #[test]
+    fn group_only_config_passes() {
+        let config = parse(
+            "[[structure.build]]\nmodule = \"entries:git\"\n\n[[groups.git.entries]]\nname = \"Shell\"\ncommand = \"sh\"\n",
+        );
+        assert!(validate(&config).is_empty());
+        assert!(missing_files(&config).is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::config::Config;
+use crate::expand::expand;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    themes_dir: PathBuf,
+    theme_files: Vec<(PathBuf, Option<SystemTime>)>,
+    last_check: Instant,
+}
+
+impl Watcher {
+    pub fn new(config: &Config) -> Watcher {
+        let mut paths = vec![Config::config_path()];
+        if let Some(ref path) = config.custom_logo_path {
+            paths.push(PathBuf::from(expand(path)));
+        }
+
+        let themes_dir = Config::themes_dir();
+        Watcher {
+            files: paths.into_iter().map(|path| (path.clone(), modified(&path))).collect(),
+            theme_files: theme_files(&themes_dir),
+            themes_dir,
+            last_check: Instant::now(),
+        }
+    }
+
+    pub fn changed(&mut self) -> bool {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+
+        let mut changed = false;
+        for (path, seen) in &mut self.files {
+            let current = modified(path);
+            if current != *seen {
+                *seen = current;
+                changed = true;
+            }
+        }
+
+        let theme_files = theme_files(&self.themes_dir);
+        if theme_files != self.theme_files {
+            self.theme_files = theme_files;
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+fn theme_files(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(PathBuf, Option<SystemTime>)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .map(|path| {
+            let modified = modified(&path);
+            (path, modified)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
@@ -335,3 +335,557 @@ quotes = ["Test quote"]
     assert_eq!(quotes[0].as_str().unwrap(), "Test quote");
 }
 
+
+#[test]
+fn test_structure_layout_options() {
+    let config_content = r#"
+[structure]
+position = "left"
+vertical = "middle"
+margin = 2
+padding = 1
+max_width = 80
+
+[[structure.build]]
+module = "logo"
+
+[[structure.build]]
+module = "entries"
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let structure = &config["structure"];
+    assert_eq!(structure["position"].as_str().unwrap(), "left");
+    assert_eq!(structure["vertical"].as_str().unwrap(), "middle");
+    assert_eq!(structure["margin"].as_integer().unwrap(), 2);
+    assert_eq!(structure["padding"].as_integer().unwrap(), 1);
+    assert_eq!(structure["max_width"].as_integer().unwrap(), 80);
+}
+
+#[test]
+fn test_nested_layout_containers() {
+    let config_content = r#"
+[structure]
+position = "center"
+
+[[structure.build]]
+module = "logo"
+
+[[structure.build]]
+row = [
+    { module = "entries", size = "40%", align = "left" },
+    { column = [ { module = "clock" }, { module = "memory" } ], size = "2fr" },
+]
+
+[[structure.build]]
+grid = [ { module = "uptime" }, { module = "disk" }, { module = "quote" } ]
+columns = 2
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let build = config["structure"]["build"].as_array().unwrap();
+    assert_eq!(build.len(), 3);
+    
+    let row = build[1]["row"].as_array().unwrap();
+    assert_eq!(row.len(), 2);
+    assert_eq!(row[0]["module"].as_str().unwrap(), "entries");
+    assert_eq!(row[0]["size"].as_str().unwrap(), "40%");
+    assert_eq!(row[0]["align"].as_str().unwrap(), "left");
+    
+    let column = row[1]["column"].as_array().unwrap();
+    assert_eq!(column[0]["module"].as_str().unwrap(), "clock");
+    assert_eq!(column[1]["module"].as_str().unwrap(), "memory");
+    assert_eq!(row[1]["size"].as_str().unwrap(), "2fr");
+    
+    assert_eq!(build[2]["grid"].as_array().unwrap().len(), 3);
+    assert_eq!(build[2]["columns"].as_integer().unwrap(), 2);
+}
+
+#[test]
+fn test_named_entry_groups() {
+    let config_content = r#"
+[structure]
+position = "center"
+
+[[structure.build]]
+module = "entries:git"
+
+[[structure.build]]
+module = "entries:servers"
+
+[[structure.build]]
+module = "entries2"
+
+[[groups.git.entries]]
+name = "Status"
+command = "git"
+args = ["status"]
+
+[[groups.git.entries]]
+name = "Log"
+command = "git"
+args = ["log", "--oneline"]
+
+[[groups.servers.entries]]
+name = "Prod"
+command = "ssh"
+args = ["prod"]
+
+[[entries2]]
+name = "Legacy"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let build = config["structure"]["build"].as_array().unwrap();
+    assert_eq!(build[0]["module"].as_str().unwrap(), "entries:git");
+    assert_eq!(build[1]["module"].as_str().unwrap(), "entries:servers");
+    
+    let groups = config["groups"].as_table().unwrap();
+    assert_eq!(groups.len(), 2);
+    
+    let git = groups["git"]["entries"].as_array().unwrap();
+    assert_eq!(git.len(), 2);
+    assert_eq!(git[1]["args"].as_array().unwrap().len(), 2);
+    
+    let servers = groups["servers"]["entries"].as_array().unwrap();
+    assert_eq!(servers[0]["name"].as_str().unwrap(), "Prod");
+    
+    assert_eq!(config["entries2"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_entry_group_metadata() {
+    let config_content = r#"
+[[structure.build]]
+module = "entries:git"
+
+[[structure.build]]
+module = "entries2"
+
+[groups.git]
+title = "Git"
+icon = "*"
+border = "rounded"
+collapsed = true
+
+[[groups.git.entries]]
+name = "Status"
+command = "git"
+args = ["status"]
+
+[groups.entries2]
+title = "Legacy Group"
+border = "double"
+
+[[entries2]]
+name = "Legacy"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let git = &config["groups"]["git"];
+    assert_eq!(git["title"].as_str().unwrap(), "Git");
+    assert_eq!(git["icon"].as_str().unwrap(), "*");
+    assert_eq!(git["border"].as_str().unwrap(), "rounded");
+    assert!(git["collapsed"].as_bool().unwrap());
+    assert_eq!(git["entries"].as_array().unwrap().len(), 1);
+    
+    let legacy = &config["groups"]["entries2"];
+    assert_eq!(legacy["title"].as_str().unwrap(), "Legacy Group");
+    assert!(legacy.get("entries").is_none());
+}
+
+#[test]
+fn test_search_options_and_tags() {
+    let config_content = r#"
+[search]
+commands = true
+tags = false
+
+[[entries]]
+name = "Deploy"
+command = "make"
+args = ["deploy"]
+tags = ["ops", "release"]
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert!(config["search"]["commands"].as_bool().unwrap());
+    assert!(!config["search"]["tags"].as_bool().unwrap());
+    
+    let tags = config["entries"][0]["tags"].as_array().unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].as_str().unwrap(), "ops");
+}
+
+#[test]
+fn test_entry_hotkeys() {
+    let config_content = r#"
+[hotkeys]
+numbers = false
+show = true
+
+[[entries]]
+name = "Git"
+command = "lazygit"
+args = []
+key = "g"
+
+[[entries]]
+name = "Monitor"
+command = "btop"
+args = []
+key = "ctrl+b"
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert!(!config["hotkeys"]["numbers"].as_bool().unwrap());
+    assert!(config["hotkeys"]["show"].as_bool().unwrap());
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["key"].as_str().unwrap(), "g");
+    assert_eq!(entries[1]["key"].as_str().unwrap(), "ctrl+b");
+}
+
+#[test]
+fn test_mouse_options() {
+    let config_content = r#"
+[mouse]
+enabled = true
+hover = false
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert!(config["mouse"]["enabled"].as_bool().unwrap());
+    assert!(!config["mouse"]["hover"].as_bool().unwrap());
+}
+
+#[test]
+fn test_theme_section() {
+    let config_content = r##"
+[theme]
+name = "nord"
+logo = "#88c0d0"
+entry = 250
+entry_selected = { fg = "black", bg = "cyan", modifiers = ["bold"] }
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"##;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let theme = &config["theme"];
+    assert_eq!(theme["name"].as_str().unwrap(), "nord");
+    assert_eq!(theme["logo"].as_str().unwrap(), "#88c0d0");
+    assert_eq!(theme["entry"].as_integer().unwrap(), 250);
+    assert_eq!(theme["entry_selected"]["bg"].as_str().unwrap(), "cyan");
+    assert_eq!(theme["entry_selected"]["modifiers"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_theme_by_name() {
+    let config_content = r#"
+theme = "nord"
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert_eq!(config["theme"].as_str().unwrap(), "nord");
+}
+
+#[test]
+fn test_bundled_theme_files() {
+    for name in ["default", "nord", "gruvbox", "dracula", "mono"] {
+        let path = format!("{}/themes/{}.toml", env!("CARGO_MANIFEST_DIR"), name);
+        let content = std::fs::read_to_string(&path).expect("Failed to read theme");
+        let theme: toml::Value = toml::from_str(&content).expect("Failed to parse theme");
+        
+        assert!(theme.get("logo").is_some(), "{} has no logo role", name);
+        assert!(theme.get("entry_selected").is_some(), "{} has no entry_selected role", name);
+    }
+}
+
+#[test]
+fn test_key_bindings() {
+    let config_content = r#"
+[keys]
+quit = ["q", "ctrl+c"]
+reload = "r"
+page_down = ["pagedown", "ctrl+f"]
+search = []
+
+[[entries]]
+name = "Test"
+command = "cmd"
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let keys = &config["keys"];
+    assert_eq!(keys["quit"].as_array().unwrap().len(), 2);
+    assert_eq!(keys["reload"].as_str().unwrap(), "r");
+    assert_eq!(keys["page_down"].as_array().unwrap()[1].as_str().unwrap(), "ctrl+f");
+    assert!(keys["search"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_entry_execution_options() {
+    let config_content = r#"
+[[entries]]
+name = "Recent Commits"
+command = "git log --oneline | head -20"
+shell = true
+cwd = "~/projects"
+env = { GIT_PAGER = "cat", LANG = "C" }
+clear_env = true
+
+[[entries]]
+name = "Plain"
+command = "ls"
+args = ["-la"]
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert!(entries[0]["shell"].as_bool().unwrap());
+    assert_eq!(entries[0]["cwd"].as_str().unwrap(), "~/projects");
+    assert_eq!(entries[0]["env"]["GIT_PAGER"].as_str().unwrap(), "cat");
+    assert!(entries[0]["clear_env"].as_bool().unwrap());
+    assert!(entries[1].get("shell").is_none());
+    assert!(entries[1].get("env").is_none());
+}
+
+#[test]
+fn test_entry_expand_opt_out() {
+    let config_content = r#"
+custom_logo_path = "$XDG_CONFIG_HOME/dott/logo.txt"
+
+[[entries]]
+name = "Previous Commit"
+command = "git"
+args = ["show", "HEAD~1"]
+
+[[entries]]
+name = "Literal"
+command = "echo"
+args = ["$HOME"]
+expand = false
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert_eq!(config["custom_logo_path"].as_str().unwrap(), "$XDG_CONFIG_HOME/dott/logo.txt");
+    let entries = config["entries"].as_array().unwrap();
+    assert!(entries[0].get("expand").is_none());
+    assert!(!entries[1]["expand"].as_bool().unwrap());
+}
+
+#[test]
+fn test_launch_options() {
+    let config_content = r#"
+[launch]
+pause = "error"
+show_success = false
+
+[[entries]]
+name = "Git Status"
+command = "git"
+args = ["status"]
+pause = "always"
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert_eq!(config["launch"]["pause"].as_str().unwrap(), "error");
+    assert!(!config["launch"]["show_success"].as_bool().unwrap());
+    assert_eq!(config["entries"][0]["pause"].as_str().unwrap(), "always");
+}
+
+#[test]
+fn test_entry_launch_modes() {
+    let config_content = r#"
+[[entries]]
+name = "Browser"
+command = "firefox"
+mode = "detach"
+
+[[entries]]
+name = "Dev Server"
+command = "npm"
+args = ["run", "dev"]
+mode = "background"
+
+[[entries]]
+name = "Disk Space"
+command = "df"
+args = ["-h"]
+mode = "capture"
+
+[[entries]]
+name = "Shell"
+command = "bash"
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["mode"].as_str().unwrap(), "detach");
+    assert_eq!(entries[1]["mode"].as_str().unwrap(), "background");
+    assert_eq!(entries[2]["mode"].as_str().unwrap(), "capture");
+    assert!(entries[3].get("mode").is_none());
+}
+
+#[test]
+fn test_entry_confirm() {
+    let config_content = r#"
+[[entries]]
+name = "Reboot"
+command = "systemctl"
+args = ["reboot"]
+confirm = "Really reboot?"
+
+[[entries]]
+name = "Force Push"
+command = "git"
+args = ["push", "--force"]
+confirm = true
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["confirm"].as_str().unwrap(), "Really reboot?");
+    assert!(entries[1]["confirm"].as_bool().unwrap());
+}
+
+#[test]
+fn test_entry_argument_placeholders() {
+    let config_content = r#"
+[[entries]]
+name = "SSH"
+command = "ssh"
+args = ["{prompt:Host}"]
+
+[[entries]]
+name = "Deploy"
+command = "./deploy.sh"
+args = ["--env={choice:dev,staging,prod}"]
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["args"][0].as_str().unwrap(), "{prompt:Host}");
+    assert_eq!(entries[1]["args"][0].as_str().unwrap(), "--env={choice:dev,staging,prod}");
+}
+
+#[test]
+fn test_entry_builtin_actions() {
+    let config_content = r#"
+[[entries]]
+name = "Beenden"
+action = "quit"
+
+[[entries]]
+name = "Settings"
+action = "edit_config"
+
+[[entries]]
+name = "Quit"
+command = ""
+args = []
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    let entries = config["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["action"].as_str().unwrap(), "quit");
+    assert!(entries[0].get("command").is_none());
+    assert_eq!(entries[1]["action"].as_str().unwrap(), "edit_config");
+}
+
+#[test]
+fn test_editor_and_reload_settings() {
+    let config_content = r#"
+editor = "code --wait"
+auto_reload = false
+
+[[entries]]
+name = "Settings"
+action = "edit_config"
+"#;
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse config");
+    
+    assert_eq!(config["editor"].as_str().unwrap(), "code --wait");
+    assert!(!config["auto_reload"].as_bool().unwrap());
+}
+
+#[test]
+fn test_profile_is_a_full_config() {
+    let profile_content = r#"
+theme = "nord"
+
+[[structure.build]]
+module = "entries:servers"
+
+[[groups.servers.entries]]
+name = "Prod"
+command = "ssh"
+args = ["prod"]
+"#;
+    
+    let config: toml::Value = toml::from_str(profile_content).expect("Failed to parse profile");
+    
+    assert_eq!(config["theme"].as_str().unwrap(), "nord");
+    let entries = config["groups"]["servers"]["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["args"][0].as_str().unwrap(), "prod");
+}
+
+#[test]
+fn test_init_template_parses() {
+    let config_content = include_str!("../src/template.toml");
+    
+    let config: toml::Value = toml::from_str(config_content).expect("Failed to parse template");
+    
+    let build = config["structure"]["build"].as_array().unwrap();
+    let modules: Vec<&str> = build.iter().map(|item| item["module"].as_str().unwrap()).collect();
+    assert_eq!(modules, ["logo", "entries", "help"]);
+    assert_eq!(config["entries"].as_array().unwrap().len(), 4);
+    assert_eq!(config["custom"]["break"]["lines"].as_integer().unwrap(), 2);
+}